@@ -0,0 +1,35 @@
+//! Benchmarks matching tag unions against sets of tags.
+//!
+//! Compares [`TagUnion::matches_set`] on a `HashSet<Tag>` with
+//! [`HashedTagUnion::matches_set`] on a [`HashedTagSet`].
+
+// SPDX-FileCopyrightText: Copyright 2023 Markus Mayer
+// SPDX-License-Identifier: EUPL-1.2
+// SPDX-FileType: SOURCE
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use just_a_tag::{HashedTagSet, HashedTagUnion, Tag, TagUnion};
+use std::collections::HashSet;
+
+fn matching(c: &mut Criterion) {
+    let tags: Vec<Tag> = (0..256)
+        .map(|i| Tag::new(format!("some-rather-long-tag-name-{i}x")))
+        .collect();
+    let union: TagUnion = tags.iter().step_by(16).cloned().collect();
+    let hashed_union = HashedTagUnion::from(&union);
+
+    let set: HashSet<Tag> = tags.iter().cloned().collect();
+    let hashed_set: HashedTagSet = tags.iter().cloned().map(Into::into).collect();
+
+    let mut group = c.benchmark_group("matching");
+    group.bench_function("tag-union", |b| {
+        b.iter(|| black_box(&union).matches_set(black_box(&set)))
+    });
+    group.bench_function("hashed-tag-union", |b| {
+        b.iter(|| black_box(&hashed_union).matches_set(black_box(&hashed_set)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, matching);
+criterion_main!(benches);
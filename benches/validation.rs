@@ -0,0 +1,37 @@
+//! Benchmarks the validation of tags.
+//!
+//! Compare the scalar and vectorized character set checks by running
+//! `cargo bench` and `cargo bench --features simd`.
+
+// SPDX-FileCopyrightText: Copyright 2023 Markus Mayer
+// SPDX-License-Identifier: EUPL-1.2
+// SPDX-FileType: SOURCE
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use just_a_tag::Tag;
+
+fn validation(c: &mut Criterion) {
+    let backend = if cfg!(feature = "simd") {
+        "simd"
+    } else {
+        "scalar"
+    };
+
+    let valid = format!("{}-{}", "a1b2c3d4e5".repeat(3), "f".repeat(32));
+    assert_eq!(valid.len(), Tag::MAX_LEN);
+    let invalid = format!("{}_", &valid[..Tag::MAX_LEN - 1]);
+    assert_eq!(invalid.len(), Tag::MAX_LEN);
+
+    let mut group = c.benchmark_group(format!("validation/{backend}"));
+    group.bench_function("valid-63", |b| b.iter(|| Tag::is_valid(black_box(&valid))));
+    group.bench_function("invalid-63", |b| {
+        b.iter(|| Tag::is_valid(black_box(&invalid)))
+    });
+    group.bench_function("from-str-63", |b| {
+        b.iter(|| Tag::from_str(black_box(&valid)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, validation);
+criterion_main!(benches);
@@ -0,0 +1,142 @@
+//! Provides the [`CaseInsensitiveTag`] type, a tag preserving its original casing.
+
+// SPDX-FileCopyrightText: Copyright 2023 Markus Mayer
+// SPDX-License-Identifier: EUPL-1.2
+// SPDX-FileType: SOURCE
+
+use crate::{Tag, TagFromStringError};
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// A tag that compares and hashes ignoring ASCII case, but displays as given.
+///
+/// Unlike [`Tag::new_normalized`], this keeps the original casing, e.g. for
+/// round-tripping values to upstream systems, while still deduplicating
+/// case variants in sets.
+///
+/// ```
+/// use std::collections::HashSet;
+/// use just_a_tag::{CaseInsensitiveTag, Tag};
+///
+/// let tag = CaseInsensitiveTag::from_str("Env-Prod").unwrap();
+/// assert_eq!(tag.to_string(), "Env-Prod");
+/// assert_eq!(tag.as_tag(), &Tag::new("env-prod"));
+///
+/// let tags = HashSet::from([tag, CaseInsensitiveTag::from_str("ENV-PROD").unwrap()]);
+/// assert_eq!(tags.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CaseInsensitiveTag {
+    /// The value as it was provided.
+    original: String,
+    /// The lowercase tag used for comparisons.
+    tag: Tag,
+}
+
+impl CaseInsensitiveTag {
+    /// Parses a [`CaseInsensitiveTag`] from a string-like value.
+    ///
+    /// The value is valid if its ASCII lowercase form is a valid [`Tag`].
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str<S: AsRef<str>>(value: S) -> Result<Self, TagFromStringError> {
+        let original = value.as_ref();
+        Ok(Self {
+            tag: Tag::new_normalized(original)?,
+            original: original.into(),
+        })
+    }
+
+    /// Returns the value in its original casing.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.original
+    }
+
+    /// Returns the lowercase tag.
+    #[inline]
+    pub fn as_tag(&self) -> &Tag {
+        &self.tag
+    }
+
+    /// Consumes the value and returns the lowercase tag.
+    #[inline]
+    pub fn into_tag(self) -> Tag {
+        self.tag
+    }
+}
+
+impl From<Tag> for CaseInsensitiveTag {
+    fn from(tag: Tag) -> Self {
+        Self {
+            original: tag.as_str().into(),
+            tag,
+        }
+    }
+}
+
+impl FromStr for CaseInsensitiveTag {
+    type Err = TagFromStringError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        CaseInsensitiveTag::from_str(value)
+    }
+}
+
+impl PartialEq for CaseInsensitiveTag {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.tag == other.tag
+    }
+}
+
+impl Eq for CaseInsensitiveTag {}
+
+impl Hash for CaseInsensitiveTag {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.tag.hash(state)
+    }
+}
+
+impl Display for CaseInsensitiveTag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(&self.original)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_case_insensitive_eq() {
+        let a = CaseInsensitiveTag::from_str("Foo-Bar").unwrap();
+        let b = CaseInsensitiveTag::from_str("foo-BAR").unwrap();
+        let c = CaseInsensitiveTag::from(Tag::new("foo-bar"));
+
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+        assert_eq!(a.as_str(), "Foo-Bar");
+        assert_eq!(b.to_string(), "foo-BAR");
+        assert_eq!(c.into_tag(), "foo-bar");
+        assert_ne!(a, CaseInsensitiveTag::from_str("foo").unwrap());
+    }
+
+    #[test]
+    fn test_set_deduplication() {
+        let tags: HashSet<CaseInsensitiveTag> = ["Foo", "FOO", "foo", "Bar"]
+            .into_iter()
+            .map(|value| value.parse().unwrap())
+            .collect();
+        assert_eq!(tags.len(), 2);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(
+            CaseInsensitiveTag::from_str("Foo_Bar"),
+            Err(TagFromStringError::InvalidCharacter('_', 3))
+        );
+    }
+}
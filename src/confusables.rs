@@ -0,0 +1,94 @@
+//! Provides a lookup of non-ASCII characters that are visually confusable with ASCII ones.
+
+// SPDX-FileCopyrightText: Copyright 2023 Markus Mayer
+// SPDX-License-Identifier: EUPL-1.2
+// SPDX-FileType: SOURCE
+
+/// Returns the ASCII character that the provided character can be mistaken for,
+/// or `None` if it is ASCII or not a known confusable.
+///
+/// This covers the common homoglyphs of tag characters, i.e. Latin lookalikes from
+/// the Cyrillic and Greek scripts, fullwidth forms and dash variants. It is not an
+/// exhaustive implementation of the Unicode confusables data.
+pub(crate) fn ascii_lookalike(c: char) -> Option<char> {
+    let ascii = match c {
+        // Cyrillic
+        'а' => 'a',
+        'с' => 'c',
+        'ԁ' => 'd',
+        'е' => 'e',
+        'һ' => 'h',
+        'і' => 'i',
+        'ј' => 'j',
+        'к' => 'k',
+        'о' => 'o',
+        'р' => 'p',
+        'ԛ' => 'q',
+        'ѕ' => 's',
+        'ԝ' => 'w',
+        'х' => 'x',
+        'у' => 'y',
+        'А' => 'A',
+        'В' => 'B',
+        'Е' => 'E',
+        'К' => 'K',
+        'М' => 'M',
+        'Н' => 'H',
+        'О' => 'O',
+        'Р' => 'P',
+        'С' => 'C',
+        'Т' => 'T',
+        'Х' => 'X',
+        // Greek
+        'α' => 'a',
+        'ι' => 'i',
+        'κ' => 'k',
+        'ν' => 'v',
+        'ο' => 'o',
+        'ρ' => 'p',
+        'υ' => 'u',
+        'Α' => 'A',
+        'Β' => 'B',
+        'Ε' => 'E',
+        'Η' => 'H',
+        'Ι' => 'I',
+        'Κ' => 'K',
+        'Μ' => 'M',
+        'Ν' => 'N',
+        'Ο' => 'O',
+        'Ρ' => 'P',
+        'Τ' => 'T',
+        'Χ' => 'X',
+        // Latin
+        'ı' => 'i',
+        'ɡ' => 'g',
+        'ℓ' => 'l',
+        // Dashes
+        '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2212}' | '\u{fe63}' => '-',
+        // Fullwidth forms
+        '\u{ff0d}' => '-',
+        '\u{ff10}'..='\u{ff19}' => char::from(b'0' + (c as u32 - 0xff10) as u8),
+        '\u{ff21}'..='\u{ff3a}' => char::from(b'A' + (c as u32 - 0xff21) as u8),
+        '\u{ff41}'..='\u{ff5a}' => char::from(b'a' + (c as u32 - 0xff41) as u8),
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_lookalike() {
+        assert_eq!(ascii_lookalike('а'), Some('a'));
+        assert_eq!(ascii_lookalike('ο'), Some('o'));
+        assert_eq!(ascii_lookalike('–'), Some('-'));
+        assert_eq!(ascii_lookalike('ｚ'), Some('z'));
+        assert_eq!(ascii_lookalike('Ｚ'), Some('Z'));
+        assert_eq!(ascii_lookalike('９'), Some('9'));
+        assert_eq!(ascii_lookalike('a'), None);
+        assert_eq!(ascii_lookalike('ö'), None);
+        assert_eq!(ascii_lookalike('€'), None);
+    }
+}
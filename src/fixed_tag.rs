@@ -0,0 +1,219 @@
+//! Provides the [`FixedTag`] type, a tag stored inline without a heap allocation.
+
+// SPDX-FileCopyrightText: Copyright 2023 Markus Mayer
+// SPDX-License-Identifier: EUPL-1.2
+// SPDX-FileType: SOURCE
+
+#[cfg(feature = "alloc")]
+use crate::tag_str::TagStr;
+use crate::{Tag, TagFromStringError};
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display, Formatter};
+use core::str::FromStr;
+
+/// A tag stored inline in a `[u8; N]` buffer, e.g. on the stack.
+///
+/// Unlike [`Tag`], creating or copying a `FixedTag` never allocates on the heap,
+/// which suits hot paths and memory constrained targets. Values are validated
+/// like [`Tag`]s, and must additionally fit into `N` bytes; a capacity of 64 holds
+/// any tag.
+///
+/// ```
+/// use just_a_tag::{FixedTag, Tag, TagFromStringError};
+///
+/// let tag = FixedTag::<64>::from_str("env-prod").unwrap();
+/// assert_eq!(tag, "env-prod");
+/// assert_eq!(tag.to_string(), "env-prod");
+/// # #[cfg(feature = "alloc")]
+/// assert_eq!(Tag::from(tag), Tag::new("env-prod"));
+///
+/// assert_eq!(
+///     FixedTag::<4>::from_str("env-prod"),
+///     Err(TagFromStringError::MaxLenExceeded(8, 4))
+/// );
+/// assert!(FixedTag::<64>::from_str("env_prod").is_err());
+/// ```
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub struct FixedTag<const N: usize> {
+    /// The value, padded with zeros.
+    bytes: [u8; N],
+    /// The length of the value in bytes.
+    len: u8,
+}
+
+impl<const N: usize> FixedTag<N> {
+    /// An empty tag.
+    pub const EMPTY: FixedTag<N> = FixedTag {
+        bytes: [0; N],
+        len: 0,
+    };
+
+    /// Parses a [`FixedTag`] from a string-like value.
+    ///
+    /// Values longer than `N` bytes are rejected with [`TagFromStringError::MaxLenExceeded`],
+    /// unless they already exceed [`Tag::MAX_LEN`].
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str<S: AsRef<str>>(value: S) -> Result<Self, TagFromStringError> {
+        let value = value.as_ref();
+        if value.is_empty() {
+            return Ok(Self::EMPTY);
+        }
+
+        if value.len() > N && value.len() <= Tag::MAX_LEN {
+            return Err(TagFromStringError::MaxLenExceeded(value.len(), N));
+        }
+
+        Tag::validate(value)?;
+        let mut bytes = [0; N];
+        bytes[..value.len()].copy_from_slice(value.as_bytes());
+        Ok(Self {
+            bytes,
+            len: value.len() as u8,
+        })
+    }
+
+    /// Returns the tag as a string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len()]).expect("tags are ASCII")
+    }
+
+    /// Returns the length of the tag in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        usize::from(self.len)
+    }
+
+    /// Returns `true` if this is the empty tag.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<const N: usize> Default for FixedTag<N> {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+impl<const N: usize> From<FixedTag<N>> for Tag {
+    fn from(value: FixedTag<N>) -> Self {
+        Tag(TagStr::from(value.as_str()))
+    }
+}
+
+impl<const N: usize> FromStr for FixedTag<N> {
+    type Err = TagFromStringError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        FixedTag::from_str(value)
+    }
+}
+
+impl<const N: usize> AsRef<str> for FixedTag<N> {
+    #[inline(always)]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> PartialEq<str> for FixedTag<N> {
+    #[inline(always)]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str().eq(other)
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for FixedTag<N> {
+    #[inline(always)]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str().eq(*other)
+    }
+}
+
+impl<const N: usize> PartialOrd for FixedTag<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for FixedTag<N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<const N: usize> Debug for FixedTag<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> Display for FixedTag<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        let tag = FixedTag::<64>::from_str("foo-bar").unwrap();
+        assert_eq!(tag.as_str(), "foo-bar");
+        assert_eq!(tag.len(), 7);
+        assert_eq!(tag, "foo-bar");
+        assert_eq!(tag, FixedTag::<64>::from_str("foo-bar").unwrap());
+        assert_ne!(tag, FixedTag::<64>::from_str("foo").unwrap());
+
+        let empty = FixedTag::<64>::from_str("").unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty, FixedTag::default());
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(
+            FixedTag::<64>::from_str("foo_bar"),
+            Err(TagFromStringError::InvalidCharacter('_', 3))
+        );
+        assert_eq!(
+            FixedTag::<3>::from_str("abcd"),
+            Err(TagFromStringError::MaxLenExceeded(4, 3))
+        );
+        assert_eq!(FixedTag::<3>::from_str("abc").unwrap(), "abc");
+
+        let long = "a".repeat(Tag::MAX_LEN + 1);
+        assert_eq!(
+            FixedTag::<128>::from_str(&long),
+            Err(TagFromStringError::LimitExceeded(Tag::MAX_LEN + 1))
+        );
+        assert_eq!(
+            FixedTag::<8>::from_str(&long),
+            Err(TagFromStringError::LimitExceeded(Tag::MAX_LEN + 1))
+        );
+    }
+
+    #[test]
+    fn test_ordering_and_display() {
+        let a = FixedTag::<8>::from_str("a").unwrap();
+        let b = FixedTag::<8>::from_str("a-b").unwrap();
+        assert!(a < b);
+        assert_eq!(format!("{b:>5}"), "  a-b");
+        assert_eq!(format!("{b:?}"), r#""a-b""#);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_into_tag() {
+        let tag = FixedTag::<8>::from_str("a-b").unwrap();
+        assert_eq!(Tag::from(tag), Tag::new("a-b"));
+    }
+}
@@ -0,0 +1,266 @@
+//! Provides the [`HashedTag`] type, a [`Tag`] with a precomputed hash, and the
+//! [`HashedTagUnion`] type matching against sets of them.
+
+// SPDX-FileCopyrightText: Copyright 2023 Markus Mayer
+// SPDX-License-Identifier: EUPL-1.2
+// SPDX-FileType: SOURCE
+
+use crate::{Tag, TagUnion};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+use std::ops::Deref;
+
+/// A set of [`HashedTag`]s, which uses the cached hashes without rehashing them.
+pub type HashedTagSet = HashSet<HashedTag, BuildHasherDefault<PassThroughHasher>>;
+
+/// A [`Tag`] together with a hash computed once on construction.
+///
+/// Hashing a `HashedTag` only writes the cached `u64`. In a [`HashedTagSet`], this
+/// value is used as is, so lookups never hash the tag's string again; equality
+/// checks compare the cached hashes first.
+///
+/// The hash is computed with fixed keys, so it is predictable. Don't use hashed
+/// sets for tags from untrusted sources, which could provoke hash collisions.
+///
+/// The cache cannot live in [`Tag`] itself: since tags implement [`Borrow<str>`](std::borrow::Borrow),
+/// they must hash exactly like their string value. For the same reason, sets of
+/// `HashedTag` cannot be queried using string slices.
+///
+/// ```
+/// use just_a_tag::{HashedTag, HashedTagSet, Tag};
+///
+/// let tags: HashedTagSet = [Tag::new("foo"), Tag::new("bar")]
+///     .into_iter()
+///     .map(HashedTag::new)
+///     .collect();
+///
+/// assert!(tags.contains(&HashedTag::new(Tag::new("foo"))));
+/// assert!(!tags.contains(&HashedTag::new(Tag::new("baz"))));
+/// ```
+#[derive(Debug, Clone)]
+pub struct HashedTag {
+    hash: u64,
+    tag: Tag,
+}
+
+impl HashedTag {
+    /// Wraps the tag, computing its hash.
+    pub fn new(tag: Tag) -> Self {
+        let mut hasher = DefaultHasher::new();
+        tag.hash(&mut hasher);
+        Self {
+            hash: hasher.finish(),
+            tag,
+        }
+    }
+
+    /// Returns the wrapped tag.
+    #[inline]
+    pub fn tag(&self) -> &Tag {
+        &self.tag
+    }
+
+    /// Returns the precomputed hash.
+    #[inline]
+    pub fn hash_value(&self) -> u64 {
+        self.hash
+    }
+
+    /// Consumes the wrapper and returns the tag.
+    #[inline]
+    pub fn into_inner(self) -> Tag {
+        self.tag
+    }
+}
+
+impl From<Tag> for HashedTag {
+    fn from(value: Tag) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<HashedTag> for Tag {
+    fn from(value: HashedTag) -> Self {
+        value.tag
+    }
+}
+
+impl Deref for HashedTag {
+    type Target = Tag;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.tag
+    }
+}
+
+impl PartialEq for HashedTag {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.tag == other.tag
+    }
+}
+
+impl Eq for HashedTag {}
+
+impl Hash for HashedTag {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash)
+    }
+}
+
+impl Display for HashedTag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.tag, f)
+    }
+}
+
+/// A [`Hasher`] that passes the cached hash of a [`HashedTag`] through unchanged.
+///
+/// This is the hasher of [`HashedTagSet`]. It is intended for hashing
+/// [`HashedTag`]s only; other values are hashed with FNV-1a as a fallback.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PassThroughHasher(u64);
+
+impl Hasher for PassThroughHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0100_0000_01b3;
+
+        let mut hash = self.0 ^ OFFSET;
+        for &b in bytes {
+            hash = (hash ^ u64::from(b)).wrapping_mul(PRIME);
+        }
+        self.0 = hash;
+    }
+
+    #[inline]
+    fn write_u64(&mut self, value: u64) {
+        self.0 = value;
+    }
+}
+
+/// A [`TagUnion`] with precomputed hashes, for matching against a [`HashedTagSet`].
+///
+/// Matching a [`TagUnion`] against a `HashSet<Tag>` hashes every tag of the union for
+/// each lookup. Preparing the union once avoids this when matching it repeatedly,
+/// e.g. in a hot path.
+///
+/// ```
+/// use just_a_tag::{HashedTag, HashedTagSet, HashedTagUnion, Tag, TagUnion};
+///
+/// let union = HashedTagUnion::from(&TagUnion::from_str("foo+bar").unwrap());
+///
+/// let set: HashedTagSet = ["foo", "bar", "baz"].into_iter().map(|value| Tag::new(value).into()).collect();
+/// assert!(union.matches_set(&set));
+///
+/// let set: HashedTagSet = ["foo", "baz"].into_iter().map(|value| Tag::new(value).into()).collect();
+/// assert!(!union.matches_set(&set));
+/// ```
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct HashedTagUnion(Vec<HashedTag>);
+
+impl HashedTagUnion {
+    /// Returns `true` if all tags of this union are present in the set.
+    pub fn matches_set(&self, values: &HashedTagSet) -> bool {
+        self.0.len() <= values.len() && self.0.iter().all(|tag| values.contains(tag))
+    }
+
+    /// Returns the number of tags in this union.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if this union contains no tags.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<&TagUnion> for HashedTagUnion {
+    fn from(value: &TagUnion) -> Self {
+        Self(value.iter().cloned().map(HashedTag::new).collect())
+    }
+}
+
+impl From<TagUnion> for HashedTagUnion {
+    fn from(value: TagUnion) -> Self {
+        Self(value.into_iter().map(HashedTag::new).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_equality() {
+        let a = HashedTag::new(Tag::new("foo"));
+        let b = HashedTag::from(Tag::new("foo"));
+        let c = HashedTag::new(Tag::new("bar"));
+
+        assert_eq!(a, b);
+        assert_eq!(a.hash_value(), b.hash_value());
+        assert_ne!(a, c);
+        assert_eq!(a.tag(), "foo");
+        assert_eq!(Tag::from(c), "bar");
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let set: HashSet<HashedTag> = ["foo", "bar", "baz"]
+            .into_iter()
+            .map(|value| HashedTag::new(Tag::new(value)))
+            .collect();
+        let subset: HashSet<HashedTag> = ["foo", "baz"]
+            .into_iter()
+            .map(|value| HashedTag::new(Tag::new(value)))
+            .collect();
+
+        assert!(subset.is_subset(&set));
+        assert!(!set.is_subset(&subset));
+    }
+
+    #[test]
+    fn test_pass_through_hasher() {
+        let tag = HashedTag::new(Tag::new("foo"));
+        let mut hasher = PassThroughHasher::default();
+        tag.hash(&mut hasher);
+        assert_eq!(hasher.finish(), tag.hash_value());
+
+        let mut a = PassThroughHasher::default();
+        let mut b = PassThroughHasher::default();
+        a.write(b"foo");
+        b.write(b"bar");
+        assert_ne!(a.finish(), b.finish());
+    }
+
+    #[test]
+    fn test_union_matches_set() {
+        let set = |values: &[&str]| -> HashedTagSet {
+            values.iter().map(|&value| Tag::new(value).into()).collect()
+        };
+
+        let union = HashedTagUnion::from(TagUnion::from_str("foo+bar").unwrap());
+        assert_eq!(union.len(), 2);
+        assert!(union.matches_set(&set(&["foo", "bar"])));
+        assert!(union.matches_set(&set(&["foo", "bar", "baz"])));
+        assert!(!union.matches_set(&set(&["foo", "baz"])));
+        assert!(!union.matches_set(&set(&[])));
+
+        let empty = HashedTagUnion::default();
+        assert!(empty.is_empty());
+        assert!(empty.matches_set(&set(&[])));
+    }
+}
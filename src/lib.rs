@@ -6,10 +6,12 @@
 //! ## Tag examples
 //!
 //! ```
+//! # #[cfg(feature = "alloc")] {
 //! # use just_a_tag::Tag;
 //! assert_eq!(Tag::new("some-tag"), "some-tag");
 //! assert_eq!(Tag::from_str("some-tag").unwrap(), "some-tag");
 //! assert!(Tag::from_str("invalid-").is_err());
+//! # }
 //! ```
 //!
 //! ## Unions of tags
@@ -18,6 +20,7 @@
 //! (unsurprisingly, this time) a union of tags.
 //!
 //! ```
+//! # #[cfg(feature = "std")] {
 //! use std::collections::HashSet;
 //! use just_a_tag::{MatchesAnyTagUnion, Tag, TagUnion};
 //!
@@ -47,7 +50,29 @@
 //! // none match
 //! let set_3 = HashSet::from_iter([Tag::new("fubar"), Tag::new("bar")]);
 //! assert!(!unions.matches_set(&set_3));
+//! # }
 //! ```
+//!
+//! ## `no_std` support
+//!
+//! The crate builds without the standard library when the default `std` feature is
+//! disabled. With the `alloc` feature, which `std` implies, the [`Tag`] and [`FixedTag`]
+//! types and their error are available. Without `alloc`, the crate does not link the
+//! `alloc` crate; [`FixedTag`] and the error remain fully available, while [`Tag`]s can
+//! only be created from static strings, e.g. using [`tag!`] or [`Tag::try_from_static`].
+//! The `serde`, `arbitrary`, `rkyv`, `smartstring` and `confusables` features
+//! imply `alloc`.
+//!
+//! The following APIs require `std`:
+//!
+//! - [`TagUnion`], [`MatchesAnyTagUnion`], [`FilterByUnions`], [`FlattenTags`], [`any_matches`] and [`distinct_tags`],
+//! - [`CaseInsensitiveTag`] and [`HashedTag`],
+//! - [`ReservedTagSet`],
+//! - [`TagDisjunction`],
+//! - [`TagExpression`],
+//! - [`TagPattern`],
+//! - [`TagPolicy`], and
+//! - the [`Error`](std::error::Error) implementations of the error types.
 
 // SPDX-FileCopyrightText: Copyright 2023 Markus Mayer
 // SPDX-License-Identifier: EUPL-1.2
@@ -55,19 +80,107 @@
 
 // Only enable the `doc_cfg` feature when the `docsrs` configuration attribute is defined.
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![cfg_attr(feature = "unsafe", allow(unsafe_code))]
 #![cfg_attr(not(feature = "unsafe"), forbid(unsafe_code))]
 
-mod tag_union;
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+#[cfg(feature = "std")]
+mod case_insensitive_tag;
+#[cfg(feature = "confusables")]
+mod confusables;
+mod fixed_tag;
+#[cfg(feature = "std")]
+mod hashed_tag;
+#[cfg(feature = "std")]
+mod reserved_tag_set;
+#[cfg(feature = "simd")]
+mod simd;
+#[cfg(feature = "std")]
+mod tag_disjunction;
+#[cfg(feature = "std")]
+mod tag_expression;
+#[cfg(feature = "intern")]
+mod tag_interner;
+#[cfg(feature = "std")]
+mod tag_pattern;
+#[cfg(feature = "std")]
+mod tag_policy;
+mod tag_str;
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub mod tag_union;
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt::{Display, Formatter};
+use core::ops::Deref;
+#[cfg(feature = "alloc")]
+use core::str::FromStr;
 #[cfg(feature = "serde")]
-use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
-use std::error::Error;
-use std::fmt::{Display, Formatter};
-use std::ops::Deref;
-use std::str::FromStr;
+use serde::{de, de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
+use tag_str::TagStr;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use case_insensitive_tag::CaseInsensitiveTag;
+pub use fixed_tag::FixedTag;
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use hashed_tag::{HashedTag, HashedTagSet, HashedTagUnion, PassThroughHasher};
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use reserved_tag_set::ReservedTagSet;
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use tag_disjunction::{TagDisjunction, TagDisjunctionFromStringError};
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use tag_expression::{EmptyTagUnionError, TagExpression, TagUnionList};
+#[cfg_attr(docsrs, doc(cfg(feature = "intern")))]
+#[cfg(feature = "intern")]
+pub use tag_interner::TagInterner;
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use tag_pattern::{TagPattern, TagPatternFromStringError};
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use tag_policy::{TagPolicy, TagPolicyFromStringError};
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use tag_union::{
+    any_matches, distinct_tags, FilterByUnions, FilterMatching, FlattenTags, MatchesAnyTagUnion,
+    MatchingUnions, TagUnion, TagUnionBuilder, TagUnionFromStringError, TagUnionParser,
+};
 
-pub use tag_union::{MatchesAnyTagUnion, TagUnion, TagUnionFromStringError};
+/// Constructs a [`Tag`] from a string literal that is validated at compile time.
+///
+/// ```
+/// use just_a_tag::tag;
+/// assert_eq!(tag!("some-tag"), "some-tag");
+/// ```
+///
+/// Invalid literals are rejected by the compiler:
+///
+/// ```compile_fail
+/// use just_a_tag::tag;
+/// let tag = tag!("invalid-");
+/// ```
+#[macro_export]
+macro_rules! tag {
+    ($value:literal) => {{
+        const TAG: $crate::Tag = $crate::Tag::from_static($value);
+        TAG
+    }};
+}
 
 /// A tag name.
 ///
@@ -78,22 +191,56 @@ pub use tag_union::{MatchesAnyTagUnion, TagUnion, TagUnionFromStringError};
 /// - only lowercase alphanumeric characters or '-',
 /// - start with an alphabetic character, and
 /// - end with an alphanumeric character.
+///
+/// Since tags hash and compare like their string value, collections of tags
+/// can be queried using plain string slices:
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use std::collections::HashSet;
+/// use just_a_tag::Tag;
+///
+/// let tags = HashSet::from([Tag::new("foo"), Tag::new("bar")]);
+/// assert!(tags.contains("foo"));
+/// assert_eq!(tags.get("bar"), Some(&Tag::new("bar")));
+/// # }
+/// ```
+///
+/// ## String methods
+///
+/// Tags dereference to `str`, but only read-only inspection is intended to be used
+/// that way, such as [`starts_with`](str::starts_with), [`ends_with`](str::ends_with),
+/// [`contains`](str::contains), [`find`](str::find), [`split`](str::split) on `-`,
+/// [`bytes`](str::bytes) and [`chars`](str::chars). Prefer the dedicated methods
+/// where they exist, e.g. [`Tag::as_str`], [`Tag::len`] or [`Tag::has_label_prefix`].
+///
+/// Methods producing new strings, such as [`to_uppercase`](str::to_uppercase) or
+/// [`replace`](str::replace), return a plain `String` that is not validated as a tag.
+/// Tags never hand out `&mut str`, so they cannot be modified in place.
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub struct Tag(String);
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Text)
+)]
+pub struct Tag(TagStr);
 
 impl Tag {
     /// An empty tag.
-    pub const EMPTY: Tag = Tag(String::new());
+    pub const EMPTY: Tag = Tag(TagStr::Static(""));
 
     /// The maximum length of a tag.
     pub const MAX_LEN: usize = 63;
 
+    /// The maximum length of a dot-separated name, see [`Tag::parse_labels`].
+    pub const MAX_NAME_LEN: usize = 253;
+
     /// Constructs a new tag.
     ///
     /// ## Panics
     ///
     /// This method panics if the input is not a valid tag. If you want to avoid a panic,
-    /// use [`Tag::from_str`](Self::from_str) instead.
+    /// use [`Tag::parse`](Self::parse) instead.
     ///
     /// ## Example
     ///
@@ -101,12 +248,18 @@ impl Tag {
     /// use just_a_tag::Tag;
     /// assert_eq!(Tag::new("foo"), "foo");
     /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
     pub fn new<V: AsRef<str>>(value: V) -> Self {
         value.as_ref().parse().expect("invalid input")
     }
 
     /// Constructs a new tag without checking for validity.
     ///
+    /// ## Safety
+    ///
+    /// The caller must ensure that the value is a valid tag.
+    ///
     /// ## Example
     ///
     /// ```
@@ -115,10 +268,12 @@ impl Tag {
     /// assert_eq!(unsafe { Tag::new_unchecked("foo") }, "foo");
     /// assert_eq!(unsafe { Tag::new_unchecked("@") }, "@"); // NOTE: invalid input
     /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
     #[cfg_attr(docsrs, doc(cfg(feature = "unsafe")))]
     #[cfg(feature = "unsafe")]
     pub unsafe fn new_unchecked<V: Into<String>>(value: V) -> Self {
-        Self(value.into())
+        Self(TagStr::from(value.into()))
     }
 
     /// Parses a [`Tag`] from a string-like value.
@@ -128,192 +283,2665 @@ impl Tag {
     /// assert_eq!(Tag::from_str("some-tag").unwrap(), "some-tag");
     /// assert!(Tag::from_str("invalid-").is_err());
     /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str<S: AsRef<str>>(value: S) -> Result<Self, TagFromStringError> {
         let value = value.as_ref();
         if value.is_empty() {
             return Ok(Tag::EMPTY.clone());
         }
 
-        if value.len() > Tag::MAX_LEN {
-            return Err(TagFromStringError::LimitExceeded(value.len()));
+        Self::validate(value)?;
+        Ok(Self(TagStr::from(value)))
+    }
+
+    /// Parses a [`Tag`] from a string-like value without panicking.
+    ///
+    /// This is the fallible counterpart of [`Tag::new`](Self::new) and behaves
+    /// exactly like [`Tag::from_str`](Self::from_str).
+    ///
+    /// ```
+    /// # use just_a_tag::{Tag, TagFromStringError};
+    /// assert_eq!(Tag::parse("some-tag").unwrap(), "some-tag");
+    /// assert_eq!(
+    ///     Tag::parse("invalid-"),
+    ///     Err(TagFromStringError::MustEndAlphanumeric('-', 7))
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn parse<S: AsRef<str>>(value: S) -> Result<Self, TagFromStringError> {
+        Self::from_str(value)
+    }
+
+    /// Parses a [`Tag`] from a string-like value, ignoring surrounding ASCII whitespace.
+    ///
+    /// Error positions refer to the untrimmed input.
+    ///
+    /// ```
+    /// # use just_a_tag::{Tag, TagFromStringError};
+    /// assert_eq!(Tag::from_str_trimmed(" foo\t").unwrap(), "foo");
+    /// assert!(Tag::from_str(" foo").is_err());
+    /// assert_eq!(
+    ///     Tag::from_str_trimmed("  foo- "),
+    ///     Err(TagFromStringError::MustEndAlphanumeric('-', 5))
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    pub fn from_str_trimmed<S: AsRef<str>>(value: S) -> Result<Self, TagFromStringError> {
+        let value = value.as_ref();
+        let start = value.len()
+            - value
+                .trim_start_matches(|c: char| c.is_ascii_whitespace())
+                .len();
+        let trimmed = value.trim_matches(|c: char| c.is_ascii_whitespace());
+        Self::from_str(trimmed).map_err(|e| e.offset_by(start))
+    }
+
+    /// Parses a [`Tag`] from a string-like value, echoing the input back on error.
+    ///
+    /// This is useful for diagnostics deep in parsing pipelines. Prefer
+    /// [`Tag::from_str`](Self::from_str) on hot paths, as the error allocates.
+    ///
+    /// ```
+    /// # use just_a_tag::{Tag, TagFromStringError};
+    /// let error = Tag::from_str_verbose("foo_bar").unwrap_err();
+    /// assert_eq!(error.input, "foo_bar");
+    /// assert_eq!(error.source, TagFromStringError::InvalidCharacter('_', 3));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    pub fn from_str_verbose<S: AsRef<str>>(value: S) -> Result<Self, TagParseError> {
+        let value = value.as_ref();
+        Self::from_str(value).map_err(|source| TagParseError {
+            input: value.into(),
+            source,
+        })
+    }
+
+    /// Parses all string-like values into tags, reporting every invalid value.
+    ///
+    /// On failure, the errors are returned together with the index of the value
+    /// they belong to.
+    ///
+    /// ```
+    /// # use just_a_tag::{Tag, TagFromStringError};
+    /// let tags = Tag::validate_all(["foo", "bar"]).unwrap();
+    /// assert_eq!(tags, ["foo", "bar"]);
+    ///
+    /// let errors = Tag::validate_all(["foo", "bar-", "baz", "_"]).unwrap_err();
+    /// assert_eq!(
+    ///     errors,
+    ///     [
+    ///         (1, TagFromStringError::MustEndAlphanumeric('-', 3)),
+    ///         (3, TagFromStringError::MustStartAlphabetic('_', 0)),
+    ///     ]
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    pub fn validate_all<I, S>(inputs: I) -> Result<Vec<Tag>, Vec<(usize, TagFromStringError)>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut tags = Vec::new();
+        let mut errors = Vec::new();
+        for (index, input) in inputs.into_iter().enumerate() {
+            match Self::from_str(input) {
+                Ok(tag) => tags.push(tag),
+                Err(e) => errors.push((index, e)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(tags)
+        } else {
+            Err(errors)
         }
+    }
 
-        let mut chars = value.chars();
-        let first = chars.next().expect("tag is not empty");
-        if !first.is_ascii_lowercase() {
-            return Err(TagFromStringError::MustStartAlphabetic(first));
+    /// Checks the input against all tag rules and collects every violation.
+    ///
+    /// Unlike [`Tag::from_str`](Self::from_str), this does not stop at the first
+    /// error, which allows presenting a complete list of issues to users. Like
+    /// [`Tag::from_str`](Self::from_str), the empty string is considered valid.
+    ///
+    /// ```
+    /// # use just_a_tag::{Tag, TagFromStringError};
+    /// assert!(Tag::diagnose("foo-bar").is_empty());
+    /// assert_eq!(
+    ///     Tag::diagnose("1fo_o-"),
+    ///     [
+    ///         TagFromStringError::MustStartAlphabetic('1', 0),
+    ///         TagFromStringError::InvalidCharacter('_', 3),
+    ///         TagFromStringError::MustEndAlphanumeric('-', 5),
+    ///     ]
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    pub fn diagnose(input: &str) -> Vec<TagFromStringError> {
+        let mut errors = Vec::new();
+        if input.len() > Tag::MAX_LEN {
+            errors.push(TagFromStringError::LimitExceeded(input.len()));
         }
 
-        let mut previous = first;
-        while let Some(c) = chars.next() {
-            if !c.is_ascii_digit() && !c.is_ascii_lowercase() && c != '-' {
-                return Err(TagFromStringError::InvalidCharacter(c));
+        let mut last = None;
+        for (position, c) in input.char_indices() {
+            let mut reported = true;
+            if !c.is_ascii() {
+                errors.push(TagFromStringError::NonAscii(c, position));
+            } else if position == 0 {
+                reported = false;
+                if !c.is_ascii_lowercase() {
+                    errors.push(TagFromStringError::MustStartAlphabetic(c, position));
+                }
+            } else if !c.is_ascii_digit() && !c.is_ascii_lowercase() && c != '-' {
+                errors.push(TagFromStringError::InvalidCharacter(c, position));
+            } else {
+                reported = false;
             }
 
-            previous = c;
+            last = Some((position, c, reported));
         }
 
-        if !previous.is_ascii_lowercase() {
-            return Err(TagFromStringError::MustEndAlphanumeric(previous));
+        if let Some((position, c, false)) = last {
+            if !c.is_ascii_lowercase() {
+                errors.push(TagFromStringError::MustEndAlphanumeric(c, position));
+            }
         }
 
-        Ok(Self(value.into()))
+        errors
     }
-}
 
-impl Display for Tag {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+    /// Parses a [`Tag`] from a string-like value, rejecting empty input.
+    ///
+    /// Unlike [`Tag::from_str`](Self::from_str), this does not accept the empty string
+    /// as [`Tag::EMPTY`].
+    ///
+    /// ```
+    /// # use just_a_tag::{Tag, TagFromStringError};
+    /// assert_eq!(Tag::from_str_non_empty("some-tag").unwrap(), "some-tag");
+    /// assert_eq!(Tag::from_str_non_empty(""), Err(TagFromStringError::Empty));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    pub fn from_str_non_empty<S: AsRef<str>>(value: S) -> Result<Self, TagFromStringError> {
+        let value = value.as_ref();
+        if value.is_empty() {
+            return Err(TagFromStringError::Empty);
+        }
+
+        Self::from_str(value)
     }
-}
 
-impl Deref for Tag {
-    type Target = str;
+    /// Parses a [`Tag`] from a string-like value, rejecting non-ASCII characters that
+    /// resemble ASCII ones with a dedicated error.
+    ///
+    /// Tags are ASCII-only, so lookalikes such as a Cyrillic `а` are rejected by
+    /// [`Tag::from_str`](Self::from_str) as well, but only with a generic
+    /// [`TagFromStringError::NonAscii`] error. This reports the first such homoglyph as
+    /// [`TagFromStringError::Confusable`], naming the ASCII character it can be mistaken
+    /// for, e.g. to flag spoofing attempts. Otherwise, the value is validated as usual.
+    ///
+    /// ```
+    /// # use just_a_tag::{Tag, TagFromStringError};
+    /// assert_eq!(Tag::from_str_no_confusables("env-prod").unwrap(), "env-prod");
+    /// assert_eq!(
+    ///     Tag::from_str_no_confusables("env-pr\u{43e}d"),
+    ///     Err(TagFromStringError::Confusable('\u{43e}', 'o', 6))
+    /// );
+    /// assert_eq!(
+    ///     Tag::from_str_no_confusables("caf\u{e9}"),
+    ///     Err(TagFromStringError::NonAscii('\u{e9}', 3))
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "confusables")))]
+    #[cfg(feature = "confusables")]
+    pub fn from_str_no_confusables(input: &str) -> Result<Self, TagFromStringError> {
+        let confusable = input.char_indices().find_map(|(position, c)| {
+            confusables::ascii_lookalike(c).map(|ascii| (c, ascii, position))
+        });
+        if let Some((c, ascii, position)) = confusable {
+            return Err(TagFromStringError::Confusable(c, ascii, position));
+        }
 
-    #[inline(always)]
-    fn deref(&self) -> &Self::Target {
-        self.0.deref()
+        Self::from_str(input)
     }
-}
 
-impl PartialEq<str> for Tag {
-    #[inline(always)]
-    fn eq(&self, other: &str) -> bool {
-        self.0.eq(other)
-    }
-}
+    /// Parses a [`Tag`] from a string-like value, additionally rejecting consecutive dashes.
+    ///
+    /// Tags such as `a--b` are valid, but break some downstream systems. This accepts
+    /// only the stricter subset of tags without them, reporting the second dash of the
+    /// first run as [`TagFromStringError::ConsecutiveDash`]. All other rules are checked first.
+    ///
+    /// ```
+    /// # use just_a_tag::{Tag, TagFromStringError};
+    /// assert_eq!(Tag::from_str_no_double_dash("foo-bar").unwrap(), "foo-bar");
+    /// assert!(Tag::from_str("foo--bar").is_ok());
+    /// assert_eq!(
+    ///     Tag::from_str_no_double_dash("foo--bar"),
+    ///     Err(TagFromStringError::ConsecutiveDash(4))
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    pub fn from_str_no_double_dash<S: AsRef<str>>(value: S) -> Result<Self, TagFromStringError> {
+        let value = value.as_ref();
+        let tag = Self::from_str(value)?;
+        if let Some(position) = value.find("--") {
+            return Err(TagFromStringError::ConsecutiveDash(position + 1));
+        }
 
-impl PartialEq<&str> for Tag {
-    #[inline(always)]
-    fn eq(&self, other: &&str) -> bool {
-        self.0.eq(other)
+        Ok(tag)
     }
-}
 
-impl FromStr for Tag {
-    type Err = TagFromStringError;
+    /// Parses a [`Tag`] from a string-like value, using a stricter length limit
+    /// than [`Tag::MAX_LEN`].
+    ///
+    /// The limit is clamped to [`Tag::MAX_LEN`], the maximum allowed by RFC 1035.
+    /// Values within [`Tag::MAX_LEN`] but exceeding the limit are rejected with
+    /// [`TagFromStringError::MaxLenExceeded`]; all other rules are unchanged.
+    ///
+    /// ```
+    /// # use just_a_tag::{Tag, TagFromStringError};
+    /// assert_eq!(Tag::from_str_with_max_len("foo-bar", 10).unwrap(), "foo-bar");
+    /// assert_eq!(
+    ///     Tag::from_str_with_max_len("foo-bar-baz", 10),
+    ///     Err(TagFromStringError::MaxLenExceeded(11, 10))
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    pub fn from_str_with_max_len<S: AsRef<str>>(
+        value: S,
+        max_len: usize,
+    ) -> Result<Self, TagFromStringError> {
+        let value = value.as_ref();
+        let max_len = max_len.min(Tag::MAX_LEN);
+        if value.len() > max_len && value.len() <= Tag::MAX_LEN {
+            return Err(TagFromStringError::MaxLenExceeded(value.len(), max_len));
+        }
 
-    #[inline(always)]
-    fn from_str(value: &str) -> Result<Self, Self::Err> {
-        Tag::from_str(value)
+        Self::from_str(value)
     }
-}
 
-impl TryFrom<&str> for Tag {
-    type Error = TagFromStringError;
+    /// Splits a dot-separated name, such as a DNS name, into its labels and
+    /// parses each into a [`Tag`].
+    ///
+    /// The name must not exceed [`Tag::MAX_NAME_LEN`] characters, and must not
+    /// contain empty labels. A single trailing dot, as in fully qualified names,
+    /// is accepted. Error positions refer to the whole name.
+    ///
+    /// ```
+    /// # use just_a_tag::{Tag, TagFromStringError};
+    /// let labels = Tag::parse_labels("foo.bar.baz").unwrap();
+    /// assert_eq!(labels, ["foo", "bar", "baz"]);
+    /// assert_eq!(Tag::parse_labels("example.com.").unwrap(), ["example", "com"]);
+    ///
+    /// assert_eq!(
+    ///     Tag::parse_labels("foo.bar_baz"),
+    ///     Err(TagFromStringError::InvalidCharacter('_', 7))
+    /// );
+    /// assert_eq!(Tag::parse_labels("foo..bar"), Err(TagFromStringError::Empty));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    pub fn parse_labels<S: AsRef<str>>(name: S) -> Result<Vec<Tag>, TagFromStringError> {
+        let name = name.as_ref();
+        let name = name.strip_suffix('.').unwrap_or(name);
+        if name.len() > Tag::MAX_NAME_LEN {
+            return Err(TagFromStringError::NameLimitExceeded(name.len()));
+        }
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        value.parse()
+        let mut labels = Vec::new();
+        let mut offset = 0;
+        for label in name.split('.') {
+            let tag = Self::from_str_non_empty(label).map_err(|e| e.offset_by(offset))?;
+            labels.push(tag);
+            offset += label.len() + 1;
+        }
+
+        Ok(labels)
     }
-}
 
-impl TryFrom<String> for Tag {
-    type Error = TagFromStringError;
+    /// Determines whether the string-like value is a valid tag without constructing it.
+    ///
+    /// Like [`Tag::from_str`](Self::from_str), this treats the empty string as valid.
+    ///
+    /// ```
+    /// # use just_a_tag::Tag;
+    /// assert!(Tag::is_valid("some-tag"));
+    /// assert!(Tag::is_valid(""));
+    /// assert!(!Tag::is_valid("invalid-"));
+    /// ```
+    pub fn is_valid<S: AsRef<str>>(value: S) -> bool {
+        let value = value.as_ref();
+        value.is_empty() || Self::validate(value).is_ok()
+    }
 
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        value.parse()
+    /// Constructs a tag from a static string without allocating.
+    ///
+    /// Since this is a `const fn`, it can be used to define well-known tags as
+    /// `const` or `static` items, in which case invalid values are rejected at compile time.
+    /// See also the [`tag!`] macro.
+    ///
+    /// ## Panics
+    ///
+    /// This method panics if the input is not a valid tag.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use just_a_tag::Tag;
+    /// const FOO: Tag = Tag::from_static("foo");
+    /// assert_eq!(FOO, "foo");
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use just_a_tag::Tag;
+    /// const INVALID: Tag = Tag::from_static("invalid-");
+    /// ```
+    pub const fn from_static(value: &'static str) -> Self {
+        assert!(Self::validate_bytes(value.as_bytes()), "invalid tag");
+        Self(TagStr::Static(value))
     }
-}
 
-impl TryFrom<&String> for Tag {
-    type Error = TagFromStringError;
+    /// Parses a [`Tag`] from a static string, borrowing it instead of allocating.
+    ///
+    /// Unlike [`Tag::from_static`], this reports invalid values as an error, e.g. for
+    /// static strings that are only known at runtime, such as leaked configuration.
+    ///
+    /// ```
+    /// # use just_a_tag::{Tag, TagFromStringError};
+    /// let value: &'static str = "foo";
+    /// let tag = Tag::try_from_static(value).unwrap();
+    /// assert_eq!(tag.as_str().as_ptr(), value.as_ptr());
+    ///
+    /// assert_eq!(
+    ///     Tag::try_from_static("foo-"),
+    ///     Err(TagFromStringError::MustEndAlphanumeric('-', 3))
+    /// );
+    /// ```
+    pub fn try_from_static(value: &'static str) -> Result<Self, TagFromStringError> {
+        if !value.is_empty() {
+            Self::validate(value)?;
+        }
 
-    fn try_from(value: &String) -> Result<Self, Self::Error> {
-        value.parse()
+        Ok(Self(TagStr::Static(value)))
     }
-}
 
-#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
-#[cfg(feature = "serde")]
-impl<'de> Deserialize<'de> for Tag {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let tag = String::deserialize(deserializer)?;
-        match Tag::from_str(&tag) {
-            Ok(tag) => Ok(tag),
-            Err(e) => Err(de::Error::custom(e)),
+    /// Determines whether the bytes form a valid tag in a `const` context.
+    ///
+    /// Like [`Tag::is_valid`](Self::is_valid), this treats the empty string as valid.
+    ///
+    /// ```
+    /// # use just_a_tag::Tag;
+    /// const VALID: bool = Tag::validate_bytes(b"some-tag");
+    /// assert!(VALID);
+    /// assert!(!Tag::validate_bytes(b"invalid-"));
+    /// ```
+    pub const fn validate_bytes(bytes: &[u8]) -> bool {
+        if bytes.is_empty() {
+            return true;
         }
+
+        if bytes.len() > Tag::MAX_LEN || !bytes[0].is_ascii_lowercase() {
+            return false;
+        }
+
+        let mut i = 1;
+        while i < bytes.len() {
+            let c = bytes[i];
+            if !c.is_ascii_digit() && !c.is_ascii_lowercase() && c != b'-' {
+                return false;
+            }
+            i += 1;
+        }
+
+        bytes[bytes.len() - 1].is_ascii_lowercase()
     }
-}
 
-#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
-#[cfg(feature = "serde")]
-impl Serialize for Tag {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_str(&self.0)
+    /// Returns a [`proptest`] strategy generating valid, non-empty tags.
+    ///
+    /// Generated tags cover the full alphabet and lengths between 1 and [`Tag::MAX_LEN`]
+    /// characters, and shrink towards shorter tags.
+    ///
+    /// ```
+    /// use just_a_tag::Tag;
+    /// use proptest::prelude::*;
+    ///
+    /// proptest!(|(tag in Tag::arb())| {
+    ///     prop_assert!(Tag::is_valid(&tag));
+    /// });
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "proptest")))]
+    #[cfg(feature = "proptest")]
+    pub fn arb() -> impl proptest::strategy::Strategy<Value = Tag> {
+        use proptest::strategy::Strategy;
+        proptest::string::string_regex("[a-z]([a-z0-9-]{0,61}[a-z])?")
+            .expect("the tag pattern is valid")
+            .prop_map(|value| Self(TagStr::from(value)))
     }
-}
 
-#[derive(Debug, Eq, PartialEq)]
-pub enum TagFromStringError {
-    MustStartAlphabetic(char),
-    MustEndAlphanumeric(char),
-    InvalidCharacter(char),
-    LimitExceeded(usize),
-}
+    /// Validates a non-empty input against the tag rules.
+    fn validate(value: &str) -> Result<(), TagFromStringError> {
+        Self::validate_with(value, Rules::RFC_1035)
+    }
 
-impl Display for TagFromStringError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            TagFromStringError::MustStartAlphabetic(c) => write!(
-                f,
-                "Tag name must begin with a lowercase alphabetic character, got '{c}'"
-            ),
-            TagFromStringError::MustEndAlphanumeric(c) => write!(
-                f,
-                "Tag name must end with a lowercase alphanumeric character, got '{c}'"
-            ),
-            TagFromStringError::InvalidCharacter(c) => write!(
-                f,
-                "Tag name must only contain lowercase alphanumeric characters or '-', got '{c}'"
-            ),
-            TagFromStringError::LimitExceeded(len) => write!(
-                f,
-                "Tag name must be not longer than 63 characters, got '{len}'"
-            ),
+    /// Validates a non-empty input against the given set of rules.
+    fn validate_with(value: &str, rules: Rules) -> Result<(), TagFromStringError> {
+        if value.len() > Tag::MAX_LEN {
+            return Err(TagFromStringError::LimitExceeded(value.len()));
         }
-    }
-}
 
-impl Error for TagFromStringError {}
+        if !value.is_ascii() {
+            let (position, c) = value
+                .char_indices()
+                .find(|(_, c)| !c.is_ascii())
+                .expect("input contains a non-ASCII character");
+            return Err(TagFromStringError::NonAscii(c, position));
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let mut chars = value.char_indices();
+        let (_, first) = chars.next().expect("tag is not empty");
+        let allowed = first.is_ascii_lowercase() || (rules.leading_digit && first.is_ascii_digit());
+        if !allowed {
+            return Err(TagFromStringError::MustStartAlphabetic(first, 0));
+        }
 
-    #[test]
-    fn test_trivial() {
-        assert_eq!(Tag::from_str("test").unwrap(), "test");
-        assert_eq!(Tag::from_str("test-case").unwrap(), "test-case");
-        assert_eq!(Tag::from_str("test---12e").unwrap(), "test---12e");
-        assert!(
-            Tag::from_str("a123456789a123456789a123456789a123456789a123456789a12345678901a")
-                .is_ok()
-        );
+        // The vectorized scan only decides whether all characters are valid;
+        // the scalar loop below locates the offending character otherwise.
+        #[cfg(feature = "simd")]
+        if simd::is_tag_charset(&value.as_bytes()[1..]) {
+            let position = value.len() - 1;
+            let last = char::from(value.as_bytes()[position]);
+            if !last.is_ascii_lowercase() {
+                return Err(TagFromStringError::MustEndAlphanumeric(last, position));
+            }
+            return Ok(());
+        }
+
+        let mut previous = (0, first);
+        for (position, c) in chars {
+            let allowed = c.is_ascii_digit()
+                || c.is_ascii_lowercase()
+                || c == '-'
+                || (rules.interior_underscore && c == '_');
+            if !allowed {
+                return Err(TagFromStringError::InvalidCharacter(c, position));
+            }
+
+            previous = (position, c);
+        }
+
+        let (position, last) = previous;
+        if !last.is_ascii_lowercase() {
+            return Err(TagFromStringError::MustEndAlphanumeric(last, position));
+        }
+
+        Ok(())
     }
 
-    #[test]
-    fn test_invalid() {
-        assert!(Tag::from_str("1").is_err());
-        assert!(Tag::from_str("-").is_err());
-        assert!(Tag::from_str("a-").is_err());
-        assert!(Tag::from_str("a1").is_err());
-        assert!(Tag::from_str("a_b_c").is_err());
-        assert!(
-            Tag::from_str("a123456789a123456789a123456789a123456789a123456789a123456789012a")
-                .is_err()
-        );
+    /// Parses a [`Tag`] from a string-like value, additionally permitting `_`
+    /// as an interior character.
+    ///
+    /// Tags created this way are **not** RFC 1035 compatible and must not be
+    /// used as DNS labels. This mode exists for interoperability with systems
+    /// that use underscores; [`Tag::from_str`](Self::from_str) remains strict.
+    /// Underscores are still rejected at the start and end of the tag.
+    ///
+    /// ```
+    /// # use just_a_tag::{Tag, TagFromStringError};
+    /// assert_eq!(Tag::from_str_relaxed("foo_bar").unwrap(), "foo_bar");
+    /// assert!(Tag::from_str("foo_bar").is_err());
+    /// assert_eq!(
+    ///     Tag::from_str_relaxed("foo_"),
+    ///     Err(TagFromStringError::MustEndAlphanumeric('_', 3))
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    pub fn from_str_relaxed<S: AsRef<str>>(value: S) -> Result<Self, TagFromStringError> {
+        let value = value.as_ref();
+        if value.is_empty() {
+            return Ok(Tag::EMPTY.clone());
+        }
+
+        Self::validate_with(value, Rules::RELAXED)?;
+        Ok(Self(TagStr::from(value)))
     }
 
-    #[test]
-    #[cfg(feature = "serde")]
-    fn test_serde_de_trivial() {
-        assert_eq!(serde_json::from_str::<Tag>(r#""test""#).unwrap(), "test");
+    /// Parses a [`Tag`] from a string-like value, additionally permitting a
+    /// leading ASCII digit as per [RFC 1123](https://datatracker.ietf.org/doc/html/rfc1123#page-13).
+    ///
+    /// RFC 1035 requires labels to start with a letter, which rules out host names
+    /// such as `3dns-server`. All other rules remain unchanged; in particular, the
+    /// tag must still end with a letter, so a lone digit is rejected.
+    ///
+    /// ```
+    /// # use just_a_tag::{Tag, TagFromStringError};
+    /// assert_eq!(Tag::from_str_rfc1123("3dns-server").unwrap(), "3dns-server");
+    /// assert!(Tag::from_str("3dns-server").is_err());
+    /// assert_eq!(
+    ///     Tag::from_str_rfc1123("3"),
+    ///     Err(TagFromStringError::MustEndAlphanumeric('3', 0))
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    pub fn from_str_rfc1123<S: AsRef<str>>(value: S) -> Result<Self, TagFromStringError> {
+        let value = value.as_ref();
+        if value.is_empty() {
+            return Ok(Tag::EMPTY.clone());
+        }
+
+        Self::validate_with(value, Rules::RFC_1123)?;
+        Ok(Self(TagStr::from(value)))
+    }
+
+    /// Parses a [`Tag`] from a string-like value, converting ASCII uppercase
+    /// letters to lowercase first.
+    ///
+    /// Since RFC 1035 labels are case-insensitive, this accepts mixed case input.
+    /// Non-ASCII characters are left untouched and are rejected as usual.
+    ///
+    /// ```
+    /// # use just_a_tag::Tag;
+    /// assert_eq!(Tag::new_normalized("Foo-Bar").unwrap(), "foo-bar");
+    /// assert!(Tag::from_str("Foo-Bar").is_err());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    pub fn new_normalized<S: AsRef<str>>(value: S) -> Result<Self, TagFromStringError> {
+        Self::from_str(value.as_ref().to_ascii_lowercase())
+    }
+
+    /// Parses a [`Tag`] from a string-like value, truncating it to [`Tag::MAX_LEN`]
+    /// characters instead of failing with [`TagFromStringError::LimitExceeded`].
+    ///
+    /// If the value was truncated, any trailing `-` left by the cut is trimmed so
+    /// that truncation alone cannot produce an invalid tag. This only handles the
+    /// length: the retained part is validated like [`Tag::from_str`](Self::from_str),
+    /// and invalid characters in it still result in an error. The discarded
+    /// remainder is not validated.
+    ///
+    /// ```
+    /// # use just_a_tag::{Tag, TagFromStringError};
+    /// let long = format!("{}-suffix", "a".repeat(62));
+    /// assert_eq!(Tag::new_truncated(&long).unwrap(), "a".repeat(62));
+    /// assert_eq!(Tag::new_truncated("foo").unwrap(), "foo");
+    /// assert_eq!(
+    ///     Tag::new_truncated("foo_bar"),
+    ///     Err(TagFromStringError::InvalidCharacter('_', 3))
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    pub fn new_truncated<S: AsRef<str>>(value: S) -> Result<Self, TagFromStringError> {
+        let value = value.as_ref();
+        if value.len() <= Tag::MAX_LEN {
+            return Self::from_str(value);
+        }
+
+        let mut end = Tag::MAX_LEN;
+        while !value.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        let truncated = &value[..end];
+        let trimmed = match truncated.trim_end_matches('-') {
+            "" => truncated,
+            trimmed => trimmed,
+        };
+
+        Self::validate(trimmed)?;
+        Ok(Self(TagStr::from(trimmed)))
+    }
+
+    /// Converts arbitrary text, such as a title, into a tag on a best-effort basis.
+    ///
+    /// ASCII letters are lowercased and every run of other characters is replaced
+    /// by a single `-`. The result is truncated to [`Tag::MAX_LEN`] characters, and
+    /// anything that is not a letter is trimmed from its start and end.
+    /// Returns `None` if nothing valid remains.
+    ///
+    /// ```
+    /// # use just_a_tag::Tag;
+    /// assert_eq!(Tag::slugify("Hello, World!").unwrap(), "hello-world");
+    /// assert_eq!(Tag::slugify("  Release Notes -- Draft ").unwrap(), "release-notes-draft");
+    /// assert_eq!(Tag::slugify("Café Olé").unwrap(), "caf-ol");
+    /// assert_eq!(Tag::slugify("?!"), None);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    pub fn slugify(input: &str) -> Option<Tag> {
+        let mut slug = String::with_capacity(input.len().min(Tag::MAX_LEN + 1));
+        for c in input.chars() {
+            let c = c.to_ascii_lowercase();
+            if c.is_ascii_lowercase() || c.is_ascii_digit() {
+                if slug.is_empty() && !c.is_ascii_lowercase() {
+                    continue;
+                }
+                slug.push(c);
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+
+            if slug.len() > Tag::MAX_LEN {
+                break;
+            }
+        }
+
+        slug.truncate(Tag::MAX_LEN);
+        let end = slug
+            .trim_end_matches(|c: char| !c.is_ascii_lowercase())
+            .len();
+        slug.truncate(end);
+        if slug.is_empty() {
+            return None;
+        }
+
+        debug_assert!(Tag::is_valid(&slug));
+        Some(Self(TagStr::from(slug)))
+    }
+
+    /// Normalizes the input by trimming surrounding whitespace and converting ASCII
+    /// uppercase letters to lowercase, then validates the result.
+    ///
+    /// The input is only copied if lowercasing is required; otherwise the returned
+    /// value borrows from it. Error positions refer to the trimmed input.
+    ///
+    /// ```
+    /// # use std::borrow::Cow;
+    /// # use just_a_tag::Tag;
+    /// assert!(matches!(Tag::normalize_cow(" foo-bar "), Ok(Cow::Borrowed("foo-bar"))));
+    /// assert!(matches!(Tag::normalize_cow("Foo-Bar"), Ok(Cow::Owned(_))));
+    /// assert!(Tag::normalize_cow("foo_bar").is_err());
+    ///
+    /// let tag = Tag::from_str(Tag::normalize_cow(" Foo ").unwrap()).unwrap();
+    /// assert_eq!(tag, "foo");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    pub fn normalize_cow(input: &str) -> Result<Cow<'_, str>, TagFromStringError> {
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            return Ok(Cow::Borrowed(trimmed));
+        }
+
+        if trimmed.bytes().any(|b| b.is_ascii_uppercase()) {
+            let lowercase = trimmed.to_ascii_lowercase();
+            Self::validate(&lowercase)?;
+            Ok(Cow::Owned(lowercase))
+        } else {
+            Self::validate(trimmed)?;
+            Ok(Cow::Borrowed(trimmed))
+        }
+    }
+
+    /// Returns the tag as a string slice.
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// # use just_a_tag::Tag;
+    /// let tag = Tag::new("foo");
+    /// assert_eq!(tag.as_str(), "foo");
+    /// # }
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the tag as a byte slice.
+    ///
+    /// Since tags only consist of ASCII characters, every byte is a character.
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// # use just_a_tag::Tag;
+    /// let tag = Tag::new("foo");
+    /// assert_eq!(tag.as_bytes(), b"foo");
+    /// assert_eq!(tag, b"foo"[..]);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    /// Returns the tag in uppercase, e.g. for presentation.
+    ///
+    /// Since tags are ASCII-only, this is cheaper than [`str::to_uppercase`]. The result
+    /// is a plain `String` and **not** a valid tag, as tags must be lowercase.
+    ///
+    /// ```
+    /// # use just_a_tag::Tag;
+    /// let tag = Tag::new("env-prod");
+    /// assert_eq!(tag.to_ascii_uppercase_string(), "ENV-PROD");
+    /// assert!(!Tag::is_valid(tag.to_ascii_uppercase_string()));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    pub fn to_ascii_uppercase_string(&self) -> String {
+        self.0.to_ascii_uppercase()
+    }
+
+    /// Returns the length of the tag.
+    ///
+    /// Since tags only consist of ASCII characters, this is both the number of
+    /// characters and the number of bytes. It is never greater than [`Tag::MAX_LEN`].
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// # use just_a_tag::Tag;
+    /// assert_eq!(Tag::new("foo").len(), 3);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if this is the [`Tag::EMPTY`] tag.
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// # use just_a_tag::Tag;
+    /// assert!(Tag::EMPTY.is_empty());
+    /// assert!(!Tag::new("foo").is_empty());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the [`LengthClass`] of the tag, e.g. for partitioning tags by length.
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// # use just_a_tag::{LengthClass, Tag};
+    /// assert_eq!(Tag::new("env-prod").length_class(), LengthClass::Short);
+    /// assert_eq!(Tag::new("env-production").length_class(), LengthClass::Medium);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn length_class(&self) -> LengthClass {
+        LengthClass::of(self.len())
+    }
+
+    /// Returns `true` if this tag equals the prefix, or starts with the prefix followed by `-`.
+    ///
+    /// This allows dash-separated tags to be treated hierarchically, in which
+    /// `region-eu` is the parent of `region-eu-west`, but not of `region-europe`.
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// # use just_a_tag::Tag;
+    /// let tag = Tag::new("region-eu-west");
+    /// assert!(tag.has_label_prefix("region-eu"));
+    /// assert!(tag.has_label_prefix("region-eu-west"));
+    /// assert!(!Tag::new("region-europe").has_label_prefix("region-eu"));
+    /// # }
+    /// ```
+    pub fn has_label_prefix(&self, prefix: &str) -> bool {
+        match self.0.strip_prefix(prefix) {
+            Some(rest) => rest.is_empty() || rest.starts_with('-'),
+            None => false,
+        }
+    }
+
+    /// Returns the number of `-`-separated segments, i.e. the depth of the tag
+    /// in a dash-separated hierarchy.
+    ///
+    /// Segments follow the semantics of [`str::split`]: consecutive dashes, as in
+    /// the valid tag `a--b`, delimit an empty segment. The empty tag has no segments.
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// # use just_a_tag::Tag;
+    /// assert_eq!(Tag::new("region-eu-west").segment_count(), 3);
+    /// assert_eq!(Tag::new("region").segment_count(), 1);
+    /// assert_eq!(Tag::new("a--b").segment_count(), 3);
+    /// assert_eq!(Tag::EMPTY.segment_count(), 0);
+    /// # }
+    /// ```
+    pub fn segment_count(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+
+        self.0.bytes().filter(|&b| b == b'-').count() + 1
+    }
+
+    /// Returns the `-`-separated segment at the given index, if any.
+    ///
+    /// As with [`Tag::segment_count`], consecutive dashes delimit an empty segment.
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// # use just_a_tag::Tag;
+    /// let tag = Tag::new("region-eu-west");
+    /// assert_eq!(tag.segment(0), Some("region"));
+    /// assert_eq!(tag.segment(2), Some("west"));
+    /// assert_eq!(tag.segment(3), None);
+    /// assert_eq!(Tag::new("a--b").segment(1), Some(""));
+    /// # }
+    /// ```
+    pub fn segment(&self, i: usize) -> Option<&str> {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.0.split('-').nth(i)
+    }
+
+    /// Returns the parent of this tag in a dash-separated hierarchy, i.e. the tag
+    /// without its last `-`-separated segment.
+    ///
+    /// Returns `None` for single-segment tags. Since the remainder must itself be a
+    /// valid tag, `None` is also returned if it would end with a digit or a dash,
+    /// as for `a1-b` or `a--b`. Otherwise, the remainder is valid under the rules
+    /// the tag was created with, e.g. for tags from [`Tag::from_str_relaxed`].
+    ///
+    /// ```
+    /// # use just_a_tag::Tag;
+    /// let tag = Tag::new("region-eu-west");
+    /// assert_eq!(tag.parent().unwrap(), "region-eu");
+    /// assert_eq!(tag.parent().unwrap().parent().unwrap(), "region");
+    /// assert_eq!(Tag::new("region").parent(), None);
+    ///
+    /// let tag = Tag::from_str_relaxed("foo_bar-baz").unwrap();
+    /// assert_eq!(tag.parent().unwrap(), "foo_bar");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    pub fn parent(&self) -> Option<Tag> {
+        // The remainder is a prefix of a valid tag, so only its new end needs checking.
+        let (parent, _) = self.0.rsplit_once('-')?;
+        if !parent.ends_with(|c: char| c.is_ascii_lowercase()) {
+            return None;
+        }
+
+        Some(Self(self.0.prefix(parent.len())))
+    }
+
+    /// Returns `true` if this tag is one of the reserved tags.
+    ///
+    /// This performs a linear scan; for larger or frequently checked collections,
+    /// use a [`ReservedTagSet`] instead.
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// # use just_a_tag::{tag, Tag};
+    /// const RESERVED: [Tag; 2] = [tag!("default"), tag!("system")];
+    /// assert!(Tag::new("system").is_reserved(&RESERVED));
+    /// assert!(!Tag::new("custom").is_reserved(&RESERVED));
+    /// # }
+    /// ```
+    pub fn is_reserved(&self, reserved: &[Tag]) -> bool {
+        reserved.contains(self)
+    }
+
+    /// Returns `true` if this tag equals `other` after trimming leading and
+    /// trailing `-` from `other`.
+    ///
+    /// This is a comparison helper for reconciling dirty input against stored tags,
+    /// not a constructor: `other` is neither validated nor turned into a tag.
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// # use just_a_tag::Tag;
+    /// let tag = Tag::new("foo-bar");
+    /// assert!(tag.eq_trimmed("foo-bar-"));
+    /// assert!(tag.eq_trimmed("--foo-bar"));
+    /// assert!(!tag.eq_trimmed("foo-bar_"));
+    /// # }
+    /// ```
+    pub fn eq_trimmed(&self, other: &str) -> bool {
+        self.as_str() == other.trim_matches('-')
+    }
+
+    /// Consumes the tag and returns the wrapped value as a [`String`].
+    ///
+    /// This does not reallocate unless the tag was constructed from a static string,
+    /// or is stored inline when the `smartstring` feature is enabled.
+    ///
+    /// ```
+    /// # use just_a_tag::Tag;
+    /// let value: String = Tag::new("foo").into_inner();
+    /// assert_eq!(value, "foo");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn into_inner(self) -> String {
+        self.0.into_string()
+    }
+}
+
+/// A coarse classification of tags by their [length](Tag::len).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum LengthClass {
+    /// Tags of up to [`LengthClass::SHORT_MAX_LEN`] characters, including the empty tag.
+    Short,
+    /// Tags of up to [`LengthClass::MEDIUM_MAX_LEN`] characters.
+    Medium,
+    /// Tags of up to [`Tag::MAX_LEN`] characters.
+    Long,
+}
+
+impl LengthClass {
+    /// The maximum length of a [`LengthClass::Short`] tag.
+    pub const SHORT_MAX_LEN: usize = 8;
+
+    /// The maximum length of a [`LengthClass::Medium`] tag.
+    pub const MEDIUM_MAX_LEN: usize = 24;
+
+    /// Returns the class of a tag with the specified length.
+    ///
+    /// Lengths above [`Tag::MAX_LEN`] are classified as [`LengthClass::Long`].
+    ///
+    /// ```
+    /// # use just_a_tag::LengthClass;
+    /// assert_eq!(LengthClass::of(8), LengthClass::Short);
+    /// assert_eq!(LengthClass::of(9), LengthClass::Medium);
+    /// assert_eq!(LengthClass::of(25), LengthClass::Long);
+    /// ```
+    pub const fn of(len: usize) -> Self {
+        if len <= Self::SHORT_MAX_LEN {
+            LengthClass::Short
+        } else if len <= Self::MEDIUM_MAX_LEN {
+            LengthClass::Medium
+        } else {
+            LengthClass::Long
+        }
+    }
+}
+
+/// The character rules applied when validating a tag.
+#[derive(Debug, Clone, Copy)]
+struct Rules {
+    /// Whether `_` is permitted as an interior character.
+    interior_underscore: bool,
+    /// Whether the tag may start with an ASCII digit.
+    leading_digit: bool,
+}
+
+impl Rules {
+    /// The strict RFC 1035 label rules.
+    const RFC_1035: Rules = Rules {
+        interior_underscore: false,
+        leading_digit: false,
+    };
+
+    /// The RFC 1123 label rules, permitting a leading digit.
+    #[cfg(feature = "alloc")]
+    const RFC_1123: Rules = Rules {
+        interior_underscore: false,
+        leading_digit: true,
+    };
+
+    /// The RFC 1035 rules, additionally permitting interior underscores.
+    #[cfg(feature = "alloc")]
+    const RELAXED: Rules = Rules {
+        interior_underscore: true,
+        leading_digit: false,
+    };
+}
+
+impl Display for Tag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Dereferences to the tag's value for read-only string inspection.
+/// See [String methods](Tag#string-methods) for the intended uses.
+impl Deref for Tag {
+    type Target = str;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.0.as_str()
+    }
+}
+
+impl AsRef<str> for Tag {
+    #[inline(always)]
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Tag {
+    #[inline(always)]
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for Tag {
+    #[inline(always)]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str().eq(other)
+    }
+}
+
+impl PartialEq<&str> for Tag {
+    #[inline(always)]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str().eq(*other)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+impl PartialEq<String> for Tag {
+    #[inline(always)]
+    fn eq(&self, other: &String) -> bool {
+        self.as_str().eq(other.as_str())
+    }
+}
+
+impl PartialEq<Tag> for str {
+    #[inline(always)]
+    fn eq(&self, other: &Tag) -> bool {
+        other.eq(self)
+    }
+}
+
+impl PartialEq<Tag> for &str {
+    #[inline(always)]
+    fn eq(&self, other: &Tag) -> bool {
+        other.eq(self)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+impl PartialEq<Tag> for String {
+    #[inline(always)]
+    fn eq(&self, other: &Tag) -> bool {
+        other.eq(self)
+    }
+}
+
+impl PartialEq<[u8]> for Tag {
+    #[inline(always)]
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_bytes().eq(other)
+    }
+}
+
+impl PartialEq<&[u8]> for Tag {
+    #[inline(always)]
+    fn eq(&self, other: &&[u8]) -> bool {
+        self.as_bytes().eq(*other)
+    }
+}
+
+impl PartialEq<Tag> for [u8] {
+    #[inline(always)]
+    fn eq(&self, other: &Tag) -> bool {
+        other.eq(self)
+    }
+}
+
+impl PartialEq<Tag> for &[u8] {
+    #[inline(always)]
+    fn eq(&self, other: &Tag) -> bool {
+        other.eq(self)
+    }
+}
+
+impl PartialOrd<str> for Tag {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &str) -> Option<Ordering> {
+        self.as_str().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<&str> for Tag {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &&str) -> Option<Ordering> {
+        self.as_str().partial_cmp(*other)
+    }
+}
+
+impl PartialOrd<Tag> for str {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Tag) -> Option<Ordering> {
+        self.partial_cmp(other.as_str())
+    }
+}
+
+impl PartialOrd<Tag> for &str {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Tag) -> Option<Ordering> {
+        (*self).partial_cmp(other.as_str())
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+impl FromStr for Tag {
+    type Err = TagFromStringError;
+
+    #[inline(always)]
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Tag::from_str(value)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+impl TryFrom<&str> for Tag {
+    type Error = TagFromStringError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+impl TryFrom<String> for Tag {
+    type Error = TagFromStringError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            return Ok(Tag::EMPTY.clone());
+        }
+
+        Self::validate(&value)?;
+        Ok(Self(TagStr::from(value)))
+    }
+}
+
+/// Borrows static strings and takes ownership of owned strings, without copying either.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+impl TryFrom<Cow<'static, str>> for Tag {
+    type Error = TagFromStringError;
+
+    fn try_from(value: Cow<'static, str>) -> Result<Self, Self::Error> {
+        match value {
+            Cow::Borrowed(value) => Self::try_from_static(value),
+            Cow::Owned(value) => Self::try_from(value),
+        }
+    }
+}
+
+/// Creates a single-character tag, which must be a lowercase ASCII letter.
+///
+/// ```
+/// # use just_a_tag::{Tag, TagFromStringError};
+/// assert_eq!(Tag::try_from('a').unwrap(), "a");
+/// assert_eq!(Tag::try_from('1'), Err(TagFromStringError::MustStartAlphabetic('1', 0)));
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+impl TryFrom<char> for Tag {
+    type Error = TagFromStringError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        const LETTERS: &str = "abcdefghijklmnopqrstuvwxyz";
+
+        if !value.is_ascii() {
+            return Err(TagFromStringError::NonAscii(value, 0));
+        }
+        if !value.is_ascii_lowercase() {
+            return Err(TagFromStringError::MustStartAlphabetic(value, 0));
+        }
+
+        let index = usize::from(value as u8 - b'a');
+        Ok(Self(TagStr::Static(&LETTERS[index..=index])))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+impl TryFrom<&String> for Tag {
+    type Error = TagFromStringError;
+
+    fn try_from(value: &String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+impl From<Tag> for String {
+    #[inline(always)]
+    fn from(value: Tag) -> Self {
+        value.into_inner()
+    }
+}
+
+/// Deserializes a tag from a string, validating it like [`Tag::from_str`](Tag::from_str).
+///
+/// Tags are (de)serialized as plain strings, so they behave predictably as the sole
+/// field of a `#[serde(transparent)]` struct: the wrapper uses the string form as
+/// well and still rejects invalid values. Use [`raw`](mod@raw) to skip validation
+/// for trusted data.
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use just_a_tag::Tag;
+///
+/// #[derive(Debug, Serialize, Deserialize)]
+/// #[serde(transparent)]
+/// struct Environment(Tag);
+///
+/// let env: Environment = serde_json::from_str(r#""env-prod""#).unwrap();
+/// assert_eq!(env.0, "env-prod");
+/// assert_eq!(serde_json::to_string(&env).unwrap(), r#""env-prod""#);
+/// assert!(serde_json::from_str::<Environment>(r#""env_prod""#).is_err());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Tag {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(TagVisitor)
+    }
+}
+
+/// Validates borrowed input before allocating, and reuses owned input as-is.
+#[cfg(feature = "serde")]
+struct TagVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> Visitor<'de> for TagVisitor {
+    type Value = Tag;
+
+    fn expecting(&self, f: &mut Formatter) -> core::fmt::Result {
+        f.write_str("a tag")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Tag::from_str(v).map_err(de::Error::custom)
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Tag::try_from(v).map_err(de::Error::custom)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let s = core::str::from_utf8(v)
+            .map_err(|_| de::Error::invalid_value(de::Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let s = String::from_utf8(v)
+            .map_err(|e| de::Error::invalid_value(de::Unexpected::Bytes(e.as_bytes()), &self))?;
+        self.visit_string(s)
+    }
+}
+
+/// (De)serializes a [`Tag`] without validating it, for data from trusted sources.
+///
+/// Use it via `#[serde(with = "just_a_tag::raw")]`, e.g. when loading tags
+/// previously written by this crate. Serialization is identical to the regular
+/// [`Serialize`] implementation.
+///
+/// **Note:** like [`Tag::new_unchecked`], this accepts any string and may produce
+/// tags that violate the tag rules. Never use it for untrusted input.
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use just_a_tag::Tag;
+///
+/// #[derive(Serialize, Deserialize)]
+/// #[serde(transparent)]
+/// struct Trusted(#[serde(with = "just_a_tag::raw")] Tag);
+///
+/// let trusted: Trusted = serde_json::from_str(r#""env-prod""#).unwrap();
+/// assert_eq!(trusted.0, "env-prod");
+/// assert_eq!(serde_json::to_string(&trusted).unwrap(), r#""env-prod""#);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "unsafe"))))]
+#[cfg(all(feature = "serde", feature = "unsafe"))]
+pub mod raw {
+    use crate::{Tag, TagStr};
+    use alloc::string::String;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serializes the tag as a string.
+    pub fn serialize<S>(value: &Tag, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value.serialize(serializer)
+    }
+
+    /// Deserializes a tag from a string without validating it.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Tag, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Ok(Tag(TagStr::from(value)))
+    }
+}
+
+/// Deserializes a sequence of tags, silently dropping entries that are not valid tags.
+///
+/// Use it via `#[serde(deserialize_with = "just_a_tag::skip_invalid")]` on a `Vec<Tag>`
+/// field for resilient loading of configuration.
+///
+/// **Note:** invalid entries are discarded without notice, so the result may contain
+/// fewer tags than the input. Entries that are not strings at all are still rejected.
+///
+/// ```
+/// use serde::Deserialize;
+/// use just_a_tag::Tag;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     #[serde(deserialize_with = "just_a_tag::skip_invalid")]
+///     tags: Vec<Tag>,
+/// }
+///
+/// let config: Config = serde_json::from_str(r#"{"tags":["foo","Bar","baz-"]}"#).unwrap();
+/// assert_eq!(config.tags, ["foo"]);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg(feature = "serde")]
+pub fn skip_invalid<'de, D>(deserializer: D) -> Result<Vec<Tag>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let tags = Vec::<LenientTag>::deserialize(deserializer)?;
+    Ok(tags.into_iter().filter_map(|tag| tag.0).collect())
+}
+
+/// A tag that deserializes to `None` if the value is not a valid tag.
+#[cfg(feature = "serde")]
+struct LenientTag(Option<Tag>);
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for LenientTag {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(LenientTagVisitor)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct LenientTagVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> Visitor<'de> for LenientTagVisitor {
+    type Value = LenientTag;
+
+    fn expecting(&self, f: &mut Formatter) -> core::fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(LenientTag(Tag::from_str(v).ok()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(LenientTag(Tag::try_from(v).ok()))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg(feature = "serde")]
+impl Serialize for Tag {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "arbitrary")))]
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Tag {
+    /// Generates a valid, non-empty tag.
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+        const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789-";
+
+        let len = u.int_in_range(1..=Tag::MAX_LEN)?;
+        let mut value = String::with_capacity(len);
+        value.push(*u.choose(LETTERS)? as char);
+        for _ in 2..len {
+            value.push(*u.choose(ALPHABET)? as char);
+        }
+        if len > 1 {
+            value.push(*u.choose(LETTERS)? as char);
+        }
+
+        debug_assert!(Tag::is_valid(&value));
+        Ok(Self(TagStr::from(value)))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (1, None)
+    }
+}
+
+/// Archives the tag as an [`ArchivedString`](rkyv::string::ArchivedString),
+/// which dereferences to `str`.
+#[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+#[cfg(feature = "rkyv")]
+impl rkyv::Archive for Tag {
+    type Archived = rkyv::string::ArchivedString;
+    type Resolver = rkyv::string::StringResolver;
+
+    #[inline]
+    fn resolve(&self, resolver: Self::Resolver, out: rkyv::Place<Self::Archived>) {
+        rkyv::string::ArchivedString::resolve_from_str(self.as_str(), resolver, out);
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+#[cfg(feature = "rkyv")]
+impl<S> rkyv::Serialize<S> for Tag
+where
+    S: rkyv::rancor::Fallible + ?Sized,
+    S::Error: rkyv::rancor::Source,
+    str: rkyv::SerializeUnsized<S>,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        rkyv::string::ArchivedString::serialize_from_str(self.as_str(), serializer)
+    }
+}
+
+/// Validates the archived string, as archives may originate from untrusted sources.
+#[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+#[cfg(feature = "rkyv")]
+impl<D> rkyv::Deserialize<Tag, D> for rkyv::string::ArchivedString
+where
+    D: rkyv::rancor::Fallible + ?Sized,
+    D::Error: rkyv::rancor::Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<Tag, D::Error> {
+        Tag::from_str(self.as_str()).map_err(rkyv::rancor::Source::new)
+    }
+}
+
+/// Maps the tag to the database's text type (e.g. `TEXT` or `VARCHAR`).
+#[cfg_attr(docsrs, doc(cfg(feature = "sqlx")))]
+#[cfg(feature = "sqlx")]
+impl<DB> sqlx::Type<DB> for Tag
+where
+    DB: sqlx::Database,
+    String: sqlx::Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as sqlx::Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as sqlx::Type<DB>>::compatible(ty)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "sqlx")))]
+#[cfg(feature = "sqlx")]
+impl<'q, DB> sqlx::Encode<'q, DB> for Tag
+where
+    DB: sqlx::Database,
+    String: sqlx::Encode<'q, DB>,
+{
+    fn encode(
+        self,
+        buf: &mut <DB as sqlx::Database>::ArgumentBuffer<'q>,
+    ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+        self.into_inner().encode(buf)
+    }
+
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as sqlx::Database>::ArgumentBuffer<'q>,
+    ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+        String::from(self.as_str()).encode(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Decodes the tag from the database's text type, validating the value.
+/// Invalid values are reported as a [`TagParseError`].
+#[cfg_attr(docsrs, doc(cfg(feature = "sqlx")))]
+#[cfg(feature = "sqlx")]
+impl<'r, DB> sqlx::Decode<'r, DB> for Tag
+where
+    DB: sqlx::Database,
+    String: sqlx::Decode<'r, DB>,
+{
+    fn decode(
+        value: <DB as sqlx::Database>::ValueRef<'r>,
+    ) -> Result<Self, sqlx::error::BoxDynError> {
+        let value = <String as sqlx::Decode<'r, DB>>::decode(value)?;
+        if value.is_empty() {
+            return Ok(Tag::EMPTY.clone());
+        }
+
+        match Self::validate(&value) {
+            Ok(()) => Ok(Self(TagStr::from(value))),
+            Err(source) => Err(Box::new(TagParseError {
+                input: value,
+                source,
+            })),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "diesel")))]
+#[cfg(feature = "diesel")]
+impl<DB> diesel::serialize::ToSql<diesel::sql_types::Text, DB> for Tag
+where
+    DB: diesel::backend::Backend,
+    str: diesel::serialize::ToSql<diesel::sql_types::Text, DB>,
+{
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, DB>,
+    ) -> diesel::serialize::Result {
+        self.as_str().to_sql(out)
+    }
+}
+
+/// Reads the tag from a `Text` column, validating the value.
+/// Invalid values are reported as a [`TagParseError`].
+#[cfg_attr(docsrs, doc(cfg(feature = "diesel")))]
+#[cfg(feature = "diesel")]
+impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Text, DB> for Tag
+where
+    DB: diesel::backend::Backend,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        let value = String::from_sql(bytes)?;
+        if value.is_empty() {
+            return Ok(Tag::EMPTY.clone());
+        }
+
+        match Self::validate(&value) {
+            Ok(()) => Ok(Self(TagStr::from(value))),
+            Err(source) => Err(Box::new(TagParseError {
+                input: value,
+                source,
+            })),
+        }
+    }
+}
+
+/// An error that occurred while parsing a [`Tag`].
+///
+/// Character related variants carry the offending character along with
+/// its byte offset into the original input.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TagFromStringError {
+    MustStartAlphabetic(char, usize),
+    MustEndAlphanumeric(char, usize),
+    InvalidCharacter(char, usize),
+    LimitExceeded(usize),
+    Empty,
+    NameLimitExceeded(usize),
+    NonAscii(char, usize),
+    /// The length of the value, and the custom limit it exceeds.
+    MaxLenExceeded(usize, usize),
+    /// A non-ASCII character, the ASCII character it resembles, and its position.
+    Confusable(char, char, usize),
+    /// The position of a `-` directly following another `-`.
+    ConsecutiveDash(usize),
+}
+
+impl TagFromStringError {
+    /// Returns the category of this error, without its payload.
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// # use just_a_tag::{Tag, TagErrorKind};
+    /// let error = Tag::from_str("foo_bar").unwrap_err();
+    /// assert_eq!(error.kind(), TagErrorKind::Char);
+    /// assert_eq!(error.kind() as u8, 3);
+    /// # }
+    /// ```
+    pub fn kind(&self) -> TagErrorKind {
+        match self {
+            TagFromStringError::MustStartAlphabetic(..) => TagErrorKind::Start,
+            TagFromStringError::MustEndAlphanumeric(..) => TagErrorKind::End,
+            TagFromStringError::InvalidCharacter(..)
+            | TagFromStringError::NonAscii(..)
+            | TagFromStringError::Confusable(..)
+            | TagFromStringError::ConsecutiveDash(_) => TagErrorKind::Char,
+            TagFromStringError::LimitExceeded(_)
+            | TagFromStringError::MaxLenExceeded(..)
+            | TagFromStringError::Empty
+            | TagFromStringError::NameLimitExceeded(_) => TagErrorKind::Length,
+        }
+    }
+
+    /// Shifts the position of character related errors by `offset` bytes.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn offset_by(self, offset: usize) -> Self {
+        match self {
+            TagFromStringError::MustStartAlphabetic(c, pos) => {
+                TagFromStringError::MustStartAlphabetic(c, pos + offset)
+            }
+            TagFromStringError::MustEndAlphanumeric(c, pos) => {
+                TagFromStringError::MustEndAlphanumeric(c, pos + offset)
+            }
+            TagFromStringError::InvalidCharacter(c, pos) => {
+                TagFromStringError::InvalidCharacter(c, pos + offset)
+            }
+            TagFromStringError::NonAscii(c, pos) => TagFromStringError::NonAscii(c, pos + offset),
+            TagFromStringError::Confusable(c, ascii, pos) => {
+                TagFromStringError::Confusable(c, ascii, pos + offset)
+            }
+            TagFromStringError::ConsecutiveDash(pos) => {
+                TagFromStringError::ConsecutiveDash(pos + offset)
+            }
+            other => other,
+        }
+    }
+}
+
+/// An error that occurred while parsing a [`Tag`], along with the rejected input.
+///
+/// See [`Tag::from_str_verbose`].
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TagParseError {
+    /// The input that failed to parse.
+    pub input: String,
+    /// The reason the input was rejected.
+    pub source: TagFromStringError,
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+impl Display for TagParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Invalid tag '{}': {}", self.input, self.source)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+impl std::error::Error for TagParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// The category of a [`TagFromStringError`].
+///
+/// The discriminants are stable and can be used as integer codes, e.g. for metrics.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum TagErrorKind {
+    /// The tag did not start with a lowercase alphabetic character.
+    Start = 1,
+    /// The tag did not end with a lowercase alphanumeric character.
+    End = 2,
+    /// The tag contained an invalid character.
+    Char = 3,
+    /// The tag was too long, or empty where a value was required.
+    Length = 4,
+}
+
+impl Display for TagFromStringError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TagFromStringError::MustStartAlphabetic(c, pos) => write!(
+                f,
+                "Tag name must begin with a lowercase alphabetic character, got '{c}' at position {pos}"
+            ),
+            TagFromStringError::MustEndAlphanumeric(c, pos) => write!(
+                f,
+                "Tag name must end with a lowercase alphanumeric character, got '{c}' at position {pos}"
+            ),
+            TagFromStringError::InvalidCharacter(c, pos) => write!(
+                f,
+                "Tag name must only contain lowercase alphanumeric characters or '-', got '{c}' at position {pos}"
+            ),
+            TagFromStringError::LimitExceeded(len) => write!(
+                f,
+                "Tag name must be not longer than 63 characters, got '{len}'"
+            ),
+            TagFromStringError::MaxLenExceeded(len, max) => write!(
+                f,
+                "Tag name must be not longer than {max} characters, got '{len}'"
+            ),
+            TagFromStringError::Empty => write!(f, "Tag name must not be empty"),
+            TagFromStringError::NameLimitExceeded(len) => write!(
+                f,
+                "Dot-separated name must be not longer than 253 characters, got '{len}'"
+            ),
+            TagFromStringError::NonAscii(c, pos) => write!(
+                f,
+                "Tag name must only contain ASCII characters, got '{c}' at position {pos}"
+            ),
+            TagFromStringError::Confusable(c, ascii, pos) => write!(
+                f,
+                "Tag name must only contain ASCII characters, got '{c}' at position {pos}, which is confusable with '{ascii}'"
+            ),
+            TagFromStringError::ConsecutiveDash(pos) => write!(
+                f,
+                "Tag name must not contain consecutive dashes, got '-' at position {pos}"
+            ),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+impl std::error::Error for TagFromStringError {}
+
+// rkyv reports deserialization errors through `core::error::Error`.
+#[cfg(all(feature = "rkyv", not(feature = "std")))]
+impl core::error::Error for TagFromStringError {}
+
+/// Serializes the error as a structured object, e.g.
+/// `{"kind":"invalid_character","char":"_","position":1}`.
+///
+/// Character related errors carry `char` and `position` fields, length related
+/// errors carry a `length` field.
+///
+/// ```
+/// # use just_a_tag::Tag;
+/// let error = Tag::from_str("a_b").unwrap_err();
+/// assert_eq!(
+///     serde_json::to_string(&error).unwrap(),
+///     r#"{"kind":"invalid_character","char":"_","position":1}"#
+/// );
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg(feature = "serde")]
+impl Serialize for TagFromStringError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let (kind, char_at, length, limit, resembles) = match *self {
+            TagFromStringError::MustStartAlphabetic(c, pos) => {
+                ("must_start_alphabetic", Some((c, pos)), None, None, None)
+            }
+            TagFromStringError::MustEndAlphanumeric(c, pos) => {
+                ("must_end_alphanumeric", Some((c, pos)), None, None, None)
+            }
+            TagFromStringError::InvalidCharacter(c, pos) => {
+                ("invalid_character", Some((c, pos)), None, None, None)
+            }
+            TagFromStringError::NonAscii(c, pos) => ("non_ascii", Some((c, pos)), None, None, None),
+            TagFromStringError::LimitExceeded(len) => {
+                ("limit_exceeded", None, Some(len), None, None)
+            }
+            TagFromStringError::NameLimitExceeded(len) => {
+                ("name_limit_exceeded", None, Some(len), None, None)
+            }
+            TagFromStringError::MaxLenExceeded(len, max) => {
+                ("max_len_exceeded", None, Some(len), Some(max), None)
+            }
+            TagFromStringError::Confusable(c, ascii, pos) => {
+                ("confusable", Some((c, pos)), None, None, Some(ascii))
+            }
+            TagFromStringError::ConsecutiveDash(pos) => {
+                ("consecutive_dash", Some(('-', pos)), None, None, None)
+            }
+            TagFromStringError::Empty => ("empty", None, None, None, None),
+        };
+
+        let fields = 1
+            + 2 * usize::from(char_at.is_some())
+            + usize::from(length.is_some())
+            + usize::from(limit.is_some())
+            + usize::from(resembles.is_some());
+        let mut state = serializer.serialize_struct("TagFromStringError", fields)?;
+        state.serialize_field("kind", kind)?;
+        if let Some((c, pos)) = char_at {
+            state.serialize_field("char", &c)?;
+            state.serialize_field("position", &pos)?;
+        }
+        if let Some(ascii) = resembles {
+            state.serialize_field("resembles", &ascii)?;
+        }
+        if let Some(len) = length {
+            state.serialize_field("length", &len)?;
+        }
+        if let Some(max) = limit {
+            state.serialize_field("limit", &max)?;
+        }
+        state.end()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trivial() {
+        assert_eq!(Tag::from_str("test").unwrap(), "test");
+        assert_eq!(Tag::from_str("test-case").unwrap(), "test-case");
+        assert_eq!(Tag::from_str("test---12e").unwrap(), "test---12e");
+        assert!(
+            Tag::from_str("a123456789a123456789a123456789a123456789a123456789a12345678901a")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(Tag::from_str("1").is_err());
+        assert!(Tag::from_str("-").is_err());
+        assert!(Tag::from_str("a-").is_err());
+        assert!(Tag::from_str("a1").is_err());
+        assert!(Tag::from_str("a_b_c").is_err());
+        assert!(
+            Tag::from_str("a123456789a123456789a123456789a123456789a123456789a123456789012a")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(Tag::is_valid(""));
+        assert!(Tag::is_valid("test"));
+        assert!(Tag::is_valid("test---12e"));
+        assert!(!Tag::is_valid("1"));
+        assert!(!Tag::is_valid("a-"));
+        assert!(!Tag::is_valid("a_b_c"));
+        assert!(!Tag::is_valid(
+            "a123456789a123456789a123456789a123456789a123456789a123456789012a"
+        ));
+    }
+
+    #[test]
+    fn test_from_static() {
+        const FOO: Tag = Tag::from_static("foo");
+        assert_eq!(FOO, Tag::new("foo"));
+        assert_eq!(Tag::from_static(""), Tag::EMPTY);
+    }
+
+    #[test]
+    fn test_try_from_static() {
+        let value: &'static str = "foo";
+        let tag = Tag::try_from_static(value).unwrap();
+        assert_eq!(tag, "foo");
+        assert_eq!(tag.as_str().as_ptr(), value.as_ptr());
+        assert_eq!(Tag::try_from_static("").unwrap(), Tag::EMPTY);
+        assert_eq!(
+            Tag::try_from_static("1foo"),
+            Err(TagFromStringError::MustStartAlphabetic('1', 0))
+        );
+
+        let tag = Tag::try_from(Cow::Borrowed(value)).unwrap();
+        assert_eq!(tag.as_str().as_ptr(), value.as_ptr());
+
+        let owned = String::from("bar");
+        let ptr = owned.as_ptr();
+        let tag = Tag::try_from(Cow::<'static, str>::Owned(owned)).unwrap();
+        assert_eq!(tag, "bar");
+        if cfg!(not(feature = "smartstring")) {
+            assert_eq!(tag.as_str().as_ptr(), ptr);
+        }
+        assert!(Tag::try_from(Cow::Borrowed("bar-")).is_err());
+    }
+
+    #[test]
+    fn test_try_from_char() {
+        for c in 'a'..='z' {
+            assert_eq!(Tag::try_from(c).unwrap(), c.to_string());
+        }
+        for c in ['0', '9', '-', '_', 'A', ' '] {
+            assert_eq!(
+                Tag::try_from(c),
+                Err(TagFromStringError::MustStartAlphabetic(c, 0))
+            );
+        }
+        assert_eq!(
+            Tag::try_from('ä'),
+            Err(TagFromStringError::NonAscii('ä', 0))
+        );
+
+        let tags: Result<Vec<Tag>, _> = "abc".chars().map(Tag::try_from).collect();
+        assert_eq!(tags.unwrap(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_static_invalid() {
+        let _ = Tag::from_static("invalid-");
+    }
+
+    #[test]
+    fn test_macro() {
+        assert_eq!(tag!("test"), "test");
+        assert_eq!(tag!("test---12e"), "test---12e");
+        assert_eq!(tag!(""), Tag::EMPTY);
+    }
+
+    #[test]
+    fn test_validate_bytes() {
+        for value in [
+            "",
+            "test",
+            "test---12e",
+            "1",
+            "-",
+            "a-",
+            "a1",
+            "a_b_c",
+            "aöb",
+            "a123456789a123456789a123456789a123456789a123456789a12345678901a",
+            "a123456789a123456789a123456789a123456789a123456789a123456789012a",
+        ] {
+            assert_eq!(
+                Tag::validate_bytes(value.as_bytes()),
+                Tag::is_valid(value),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_as_ref() {
+        fn takes_as_ref<S: AsRef<str>>(value: S) -> String {
+            value.as_ref().to_string()
+        }
+
+        assert_eq!(takes_as_ref(Tag::new("foo")), "foo");
+    }
+
+    #[test]
+    fn test_borrow_lookup() {
+        let tags = std::collections::HashSet::from([Tag::new("foo"), Tag::from_static("bar")]);
+        assert!(tags.contains("foo"));
+        assert!(tags.contains("bar"));
+        assert!(!tags.contains("baz"));
+        assert_eq!(tags.get("foo"), Some(&Tag::new("foo")));
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn test_arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let data: Vec<u8> = (0..=255).cycle().take(4096).collect();
+        let mut u = Unstructured::new(&data);
+        for _ in 0..32 {
+            let tag = Tag::arbitrary(&mut u).unwrap();
+            assert!(!tag.is_empty());
+            assert!(Tag::is_valid(&tag), "{tag}");
+        }
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn test_proptest_valid(tag in Tag::arb()) {
+            proptest::prop_assert!(!tag.is_empty());
+            proptest::prop_assert!(tag.len() <= Tag::MAX_LEN);
+            proptest::prop_assert_eq!(Tag::from_str(&tag), Ok(tag));
+        }
+    }
+
+    #[test]
+    fn test_symmetric_eq() {
+        let tag = Tag::new("foo");
+        assert_eq!(tag, "foo");
+        assert_eq!("foo", tag);
+        assert_eq!(*"foo", tag);
+        assert_eq!(tag, String::from("foo"));
+        assert_eq!(String::from("foo"), tag);
+        assert_ne!("bar", tag);
+        assert_ne!(String::from("bar"), tag);
+    }
+
+    #[test]
+    fn test_to_ascii_uppercase_string() {
+        assert_eq!(Tag::new("foo-b4r").to_ascii_uppercase_string(), "FOO-B4R");
+        assert_eq!(Tag::EMPTY.to_ascii_uppercase_string(), "");
+    }
+
+    #[test]
+    fn test_eq_bytes() {
+        let tag = Tag::new("foo-bar");
+        assert_eq!(tag.as_bytes(), b"foo-bar");
+        assert_eq!(tag, b"foo-bar"[..]);
+        assert_eq!(tag, &b"foo-bar"[..]);
+        assert_eq!(b"foo-bar"[..], tag);
+        assert_eq!(&b"foo-bar"[..], tag);
+        assert_ne!(tag, &b"foo-baz"[..]);
+        assert_ne!(tag, &b"foo"[..]);
+        assert_ne!(&b"FOO-BAR"[..], tag);
+        assert_ne!(tag, &[0xff, 0xfe][..]);
+        assert_eq!(Tag::EMPTY, &b""[..]);
+    }
+
+    #[test]
+    fn test_display_padding() {
+        let tag = Tag::new("foo");
+        assert_eq!(format!("{tag}"), "foo");
+        assert_eq!(format!("{tag:>6}"), "   foo");
+        assert_eq!(format!("{tag:<6}|"), "foo   |");
+        assert_eq!(format!("{tag:-^7}"), "--foo--");
+        assert_eq!(format!("{tag:.2}"), "fo");
+    }
+
+    #[test]
+    fn test_symmetric_ord() {
+        let tag = Tag::new("foo");
+        assert!(tag < "goo");
+        assert!(tag > *"bar");
+        assert!("bar" < tag);
+        assert!(*"goo" > tag);
+        assert_eq!(tag.partial_cmp("foo"), Some(Ordering::Equal));
+        assert_eq!("foo".partial_cmp(&tag), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn test_into_string() {
+        let value: String = Tag::new("foo").into();
+        assert_eq!(value, "foo");
+        assert_eq!(String::from(Tag::from_static("bar")), "bar");
+    }
+
+    #[test]
+    fn test_has_label_prefix() {
+        let tag = Tag::new("region-eu-west");
+        assert!(tag.has_label_prefix("region"));
+        assert!(tag.has_label_prefix("region-eu"));
+        assert!(tag.has_label_prefix("region-eu-west"));
+        assert!(!tag.has_label_prefix("region-eu-"));
+        assert!(!tag.has_label_prefix("region-e"));
+        assert!(!tag.has_label_prefix("region-eu-west-1"));
+        assert!(!Tag::new("region-europe").has_label_prefix("region-eu"));
+    }
+
+    #[test]
+    fn test_length_class() {
+        assert_eq!(Tag::EMPTY.length_class(), LengthClass::Short);
+        assert_eq!(Tag::new("a".repeat(8)).length_class(), LengthClass::Short);
+        assert_eq!(Tag::new("a".repeat(9)).length_class(), LengthClass::Medium);
+        assert_eq!(Tag::new("a".repeat(24)).length_class(), LengthClass::Medium);
+        assert_eq!(Tag::new("a".repeat(25)).length_class(), LengthClass::Long);
+        assert_eq!(
+            Tag::new("a".repeat(Tag::MAX_LEN)).length_class(),
+            LengthClass::Long
+        );
+        assert!(LengthClass::Short < LengthClass::Medium);
+        assert!(LengthClass::Medium < LengthClass::Long);
+    }
+
+    #[test]
+    fn test_segments() {
+        let tag = Tag::new("region-eu-west");
+        assert_eq!(tag.segment_count(), 3);
+        assert_eq!(tag.segment(0), Some("region"));
+        assert_eq!(tag.segment(1), Some("eu"));
+        assert_eq!(tag.segment(2), Some("west"));
+        assert_eq!(tag.segment(3), None);
+
+        let tag = Tag::new("a--b");
+        assert_eq!(tag.segment_count(), 3);
+        assert_eq!(tag.segment(0), Some("a"));
+        assert_eq!(tag.segment(1), Some(""));
+        assert_eq!(tag.segment(2), Some("b"));
+        assert_eq!(tag.segment_count(), tag.split('-').count());
+
+        assert_eq!(Tag::new("foo").segment_count(), 1);
+        assert_eq!(Tag::new("foo").segment(0), Some("foo"));
+        assert_eq!(Tag::EMPTY.segment_count(), 0);
+        assert_eq!(Tag::EMPTY.segment(0), None);
+    }
+
+    #[test]
+    fn test_parent() {
+        let tag = Tag::new("region-eu-west");
+        let parent = tag.parent().unwrap();
+        assert_eq!(parent, "region-eu");
+        assert_eq!(parent.parent().unwrap(), "region");
+        assert_eq!(parent.parent().unwrap().parent(), None);
+        assert!(tag.has_label_prefix(&parent));
+
+        assert_eq!(Tag::new("a-b-c-d").parent().unwrap(), "a-b-c");
+        assert_eq!(Tag::new("a---b").parent(), None);
+        assert_eq!(Tag::new("a1-b").parent(), None);
+        assert_eq!(Tag::new("a1b-c").parent().unwrap(), "a1b");
+        assert_eq!(Tag::new("foo").parent(), None);
+        assert_eq!(Tag::EMPTY.parent(), None);
+    }
+
+    #[test]
+    fn test_parent_keeps_rules() {
+        let tag = Tag::from_str_relaxed("foo_bar-baz").unwrap();
+        assert_eq!(tag.parent().unwrap(), "foo_bar");
+
+        let tag = Tag::from_str_rfc1123("1abc-def").unwrap();
+        assert_eq!(tag.parent().unwrap(), "1abc");
+        assert_eq!(Tag::from_str_rfc1123("1-abc").unwrap().parent(), None);
+
+        let tag = tag!("region-eu-west");
+        assert!(matches!(tag.parent().unwrap().0, TagStr::Static("region-eu")));
+    }
+
+    #[test]
+    fn test_eq_trimmed() {
+        let tag = Tag::new("foo");
+        assert!(tag.eq_trimmed("foo"));
+        assert!(tag.eq_trimmed("foo-"));
+        assert!(tag.eq_trimmed("-foo--"));
+        assert!(!tag.eq_trimmed("foo-bar"));
+        assert!(!tag.eq_trimmed(" foo"));
+        assert!(Tag::EMPTY.eq_trimmed("--"));
+    }
+
+    #[test]
+    fn test_error_kind() {
+        assert_eq!(
+            Tag::from_str("1foo").unwrap_err().kind(),
+            TagErrorKind::Start
+        );
+        assert_eq!(Tag::from_str("foo-").unwrap_err().kind(), TagErrorKind::End);
+        assert_eq!(Tag::from_str("f_o").unwrap_err().kind(), TagErrorKind::Char);
+        assert_eq!(
+            Tag::from_str("a".repeat(64)).unwrap_err().kind(),
+            TagErrorKind::Length
+        );
+        assert_eq!(
+            Tag::from_str_non_empty("").unwrap_err().kind(),
+            TagErrorKind::Length
+        );
+    }
+
+    #[test]
+    fn test_from_str_verbose() {
+        assert_eq!(Tag::from_str_verbose("foo").unwrap(), "foo");
+
+        let error = Tag::from_str_verbose("foo-").unwrap_err();
+        assert_eq!(error.input, "foo-");
+        assert_eq!(
+            error.source,
+            TagFromStringError::MustEndAlphanumeric('-', 3)
+        );
+        assert_eq!(
+            error.to_string(),
+            "Invalid tag 'foo-': Tag name must end with a lowercase alphanumeric character, got '-' at position 3"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "diesel")]
+    fn test_diesel_roundtrip() {
+        use diesel::dsl::sql;
+        use diesel::prelude::*;
+        use diesel::sql_types::Text;
+
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+
+        let tag = Tag::new("foo-bar");
+        let read: Tag = diesel::select(tag.clone().into_sql::<Text>())
+            .get_result(&mut conn)
+            .unwrap();
+        assert_eq!(read, tag);
+
+        let invalid = diesel::select(sql::<Text>("'Foo'")).get_result::<Tag>(&mut conn);
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "sqlx")]
+    fn test_sqlx_impls() {
+        fn assert_impls<T>()
+        where
+            T: sqlx::Type<sqlx::Any>
+                + for<'q> sqlx::Encode<'q, sqlx::Any>
+                + for<'r> sqlx::Decode<'r, sqlx::Any>,
+        {
+        }
+
+        assert_impls::<Tag>();
+        assert!(<Tag as sqlx::Type<sqlx::Any>>::compatible(
+            &<String as sqlx::Type<sqlx::Any>>::type_info()
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "smartstring")]
+    fn test_smartstring_inline() {
+        let TagStr::Owned(short) = Tag::new("short-tag").0 else {
+            panic!("expected an owned tag");
+        };
+        assert!(short.is_inline());
+
+        let long = "a".repeat(Tag::MAX_LEN);
+        let TagStr::Owned(spilled) = Tag::new(&long).0 else {
+            panic!("expected an owned tag");
+        };
+        assert!(!spilled.is_inline());
+        assert_eq!(Tag::new(&long), long);
+    }
+
+    #[test]
+    #[cfg(feature = "rkyv")]
+    fn test_rkyv_roundtrip() {
+        use rkyv::rancor::Error;
+        use rkyv::string::ArchivedString;
+
+        let tag = Tag::new("some-tag");
+        let bytes = rkyv::to_bytes::<Error>(&tag).unwrap();
+        let archived = rkyv::access::<ArchivedString, Error>(&bytes).unwrap();
+        assert_eq!(&**archived, "some-tag");
+
+        let deserialized: Tag = rkyv::deserialize::<Tag, Error>(archived).unwrap();
+        assert_eq!(deserialized, tag);
+    }
+
+    #[test]
+    #[cfg(feature = "rkyv")]
+    fn test_rkyv_invalid() {
+        use rkyv::rancor::Error;
+        use rkyv::string::ArchivedString;
+
+        for value in ["Foo", "foo_bar", "foo-", &"a".repeat(Tag::MAX_LEN + 1)] {
+            let bytes = rkyv::to_bytes::<Error>(&String::from(value)).unwrap();
+            let archived = rkyv::access::<ArchivedString, Error>(&bytes).unwrap();
+            assert!(
+                rkyv::deserialize::<Tag, Error>(archived).is_err(),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_position() {
+        assert_eq!(
+            Tag::from_str("1foo"),
+            Err(TagFromStringError::MustStartAlphabetic('1', 0))
+        );
+        assert_eq!(
+            Tag::from_str("foo_bar"),
+            Err(TagFromStringError::InvalidCharacter('_', 3))
+        );
+        assert_eq!(
+            Tag::from_str("aöb"),
+            Err(TagFromStringError::NonAscii('ö', 1))
+        );
+        assert_eq!(
+            Tag::from_str("aö-"),
+            Err(TagFromStringError::NonAscii('ö', 1))
+        );
+        assert_eq!(
+            Tag::from_str("foo-"),
+            Err(TagFromStringError::MustEndAlphanumeric('-', 3))
+        );
+        assert_eq!(
+            Tag::from_str("äfoo"),
+            Err(TagFromStringError::NonAscii('ä', 0))
+        );
+        assert_eq!(
+            Tag::from_str("foo_bar€"),
+            Err(TagFromStringError::NonAscii('€', 7))
+        );
+    }
+
+    #[test]
+    fn test_normalized() {
+        assert_eq!(Tag::new_normalized("Foo-Bar").unwrap(), "foo-bar");
+        assert_eq!(Tag::new_normalized("foo").unwrap(), "foo");
+        assert_eq!(
+            Tag::new_normalized("FooÄ"),
+            Err(TagFromStringError::NonAscii('Ä', 3))
+        );
+    }
+
+    #[test]
+    fn test_from_str_trimmed() {
+        assert_eq!(Tag::from_str_trimmed("foo").unwrap(), "foo");
+        assert_eq!(Tag::from_str_trimmed(" foo ").unwrap(), "foo");
+        assert_eq!(Tag::from_str_trimmed("\r\n foo-bar\t").unwrap(), "foo-bar");
+        assert_eq!(Tag::from_str_trimmed("   ").unwrap(), Tag::EMPTY);
+        assert_eq!(
+            Tag::from_str_trimmed(" foo bar "),
+            Err(TagFromStringError::InvalidCharacter(' ', 4))
+        );
+        assert_eq!(
+            Tag::from_str_trimmed("\u{a0}foo"),
+            Err(TagFromStringError::NonAscii('\u{a0}', 0))
+        );
+    }
+
+    #[test]
+    fn test_diagnose() {
+        assert!(Tag::diagnose("").is_empty());
+        assert!(Tag::diagnose("foo-bar").is_empty());
+        assert_eq!(
+            Tag::diagnose("1"),
+            [
+                TagFromStringError::MustStartAlphabetic('1', 0),
+                TagFromStringError::MustEndAlphanumeric('1', 0),
+            ]
+        );
+        assert_eq!(
+            Tag::diagnose("foo_bar_"),
+            [
+                TagFromStringError::InvalidCharacter('_', 3),
+                TagFromStringError::InvalidCharacter('_', 7),
+            ]
+        );
+        assert_eq!(Tag::diagnose("äb"), [TagFromStringError::NonAscii('ä', 0)]);
+
+        let long = format!("{}-", "a".repeat(Tag::MAX_LEN));
+        assert_eq!(
+            Tag::diagnose(&long),
+            [
+                TagFromStringError::LimitExceeded(64),
+                TagFromStringError::MustEndAlphanumeric('-', 63),
+            ]
+        );
+
+        for value in ["foo", "1foo", "foo-", "a_b", "aöb", "Foo"] {
+            assert_eq!(
+                Tag::diagnose(value).first(),
+                Tag::from_str(value).err().as_ref(),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_labels() {
+        assert_eq!(Tag::parse_labels("foo").unwrap(), ["foo"]);
+        assert_eq!(
+            Tag::parse_labels("foo.bar.baz").unwrap(),
+            ["foo", "bar", "baz"]
+        );
+        assert_eq!(Tag::parse_labels("foo.bar.").unwrap(), ["foo", "bar"]);
+        assert_eq!(Tag::parse_labels(""), Err(TagFromStringError::Empty));
+        assert_eq!(Tag::parse_labels("."), Err(TagFromStringError::Empty));
+        assert_eq!(Tag::parse_labels(".foo"), Err(TagFromStringError::Empty));
+        assert_eq!(
+            Tag::parse_labels("foo.1bar"),
+            Err(TagFromStringError::MustStartAlphabetic('1', 4))
+        );
+        assert_eq!(
+            Tag::parse_labels("foo.bar-.baz"),
+            Err(TagFromStringError::MustEndAlphanumeric('-', 7))
+        );
+
+        let label = "a".repeat(Tag::MAX_LEN);
+        let name = [label.as_str(); 4].join(".");
+        assert_eq!(name.len(), 255);
+        assert_eq!(
+            Tag::parse_labels(&name),
+            Err(TagFromStringError::NameLimitExceeded(255))
+        );
+        let name = [label.as_str(), &label, &label, &label[..61]].join(".");
+        assert_eq!(Tag::parse_labels(&name).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_from_str_relaxed() {
+        assert_eq!(Tag::from_str_relaxed("a_b").unwrap(), "a_b");
+        assert_eq!(
+            Tag::from_str("a_b"),
+            Err(TagFromStringError::InvalidCharacter('_', 1))
+        );
+        assert_eq!(Tag::from_str_relaxed("foo-bar").unwrap(), "foo-bar");
+        assert_eq!(Tag::from_str_relaxed("").unwrap(), Tag::EMPTY);
+        assert_eq!(
+            Tag::from_str_relaxed("_ab"),
+            Err(TagFromStringError::MustStartAlphabetic('_', 0))
+        );
+        assert_eq!(
+            Tag::from_str_relaxed("ab_"),
+            Err(TagFromStringError::MustEndAlphanumeric('_', 2))
+        );
+        assert_eq!(
+            Tag::from_str_relaxed("a.b"),
+            Err(TagFromStringError::InvalidCharacter('.', 1))
+        );
+    }
+
+    #[test]
+    fn test_from_str_rfc1123() {
+        assert_eq!(Tag::from_str_rfc1123("3foo").unwrap(), "3foo");
+        assert_eq!(
+            Tag::from_str("3foo"),
+            Err(TagFromStringError::MustStartAlphabetic('3', 0))
+        );
+        assert_eq!(
+            Tag::from_str_rfc1123("3"),
+            Err(TagFromStringError::MustEndAlphanumeric('3', 0))
+        );
+        assert_eq!(Tag::from_str_rfc1123("foo").unwrap(), "foo");
+        assert_eq!(
+            Tag::from_str_rfc1123("-foo"),
+            Err(TagFromStringError::MustStartAlphabetic('-', 0))
+        );
+        assert_eq!(
+            Tag::from_str_rfc1123("3_foo"),
+            Err(TagFromStringError::InvalidCharacter('_', 1))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "confusables")]
+    fn test_from_str_no_confusables() {
+        assert_eq!(Tag::from_str_no_confusables("foo-bar").unwrap(), "foo-bar");
+        assert_eq!(Tag::from_str_no_confusables("").unwrap(), Tag::EMPTY);
+        assert_eq!(
+            Tag::from_str_no_confusables("p\u{430}y"),
+            Err(TagFromStringError::Confusable('\u{430}', 'a', 1))
+        );
+        assert_eq!(
+            Tag::from_str_no_confusables("foo\u{2013}bar"),
+            Err(TagFromStringError::Confusable('\u{2013}', '-', 3))
+        );
+        assert_eq!(
+            Tag::from_str_no_confusables("f\u{f6}o-\u{3bf}"),
+            Err(TagFromStringError::Confusable('\u{3bf}', 'o', 5))
+        );
+        assert_eq!(
+            Tag::from_str_no_confusables("f\u{f6}o"),
+            Err(TagFromStringError::NonAscii('\u{f6}', 1))
+        );
+        assert_eq!(
+            Tag::from_str_no_confusables("foo_bar"),
+            Err(TagFromStringError::InvalidCharacter('_', 3))
+        );
+        assert_eq!(
+            TagFromStringError::Confusable('\u{430}', 'a', 1).to_string(),
+            "Tag name must only contain ASCII characters, got '\u{430}' at position 1, which is confusable with 'a'"
+        );
+    }
+
+    #[test]
+    fn test_from_str_no_double_dash() {
+        assert_eq!(Tag::from_str_no_double_dash("a-b-c").unwrap(), "a-b-c");
+        assert_eq!(Tag::from_str_no_double_dash("").unwrap(), Tag::EMPTY);
+        assert_eq!(
+            Tag::from_str_no_double_dash("a--b"),
+            Err(TagFromStringError::ConsecutiveDash(2))
+        );
+        assert_eq!(
+            Tag::from_str_no_double_dash("a-b---c--d"),
+            Err(TagFromStringError::ConsecutiveDash(4))
+        );
+        assert_eq!(
+            Tag::from_str_no_double_dash("a--"),
+            Err(TagFromStringError::MustEndAlphanumeric('-', 2))
+        );
+        assert_eq!(
+            Tag::from_str_no_double_dash("a--b_c"),
+            Err(TagFromStringError::InvalidCharacter('_', 4))
+        );
+        assert_eq!(
+            TagFromStringError::ConsecutiveDash(2).kind(),
+            TagErrorKind::Char
+        );
+    }
+
+    #[test]
+    fn test_from_str_with_max_len() {
+        assert_eq!(Tag::from_str_with_max_len("foo", 10).unwrap(), "foo");
+        assert_eq!(
+            Tag::from_str_with_max_len("abcdefghij", 10).unwrap(),
+            "abcdefghij"
+        );
+        assert_eq!(
+            Tag::from_str_with_max_len("abcdefghijk", 10),
+            Err(TagFromStringError::MaxLenExceeded(11, 10))
+        );
+        assert_eq!(
+            Tag::from_str_with_max_len("foo_bar", 10),
+            Err(TagFromStringError::InvalidCharacter('_', 3))
+        );
+        assert_eq!(Tag::from_str_with_max_len("", 10).unwrap(), Tag::EMPTY);
+        assert_eq!(
+            TagFromStringError::MaxLenExceeded(11, 10).kind(),
+            TagErrorKind::Length
+        );
+
+        let full = "a".repeat(Tag::MAX_LEN);
+        assert_eq!(Tag::from_str_with_max_len(&full, 100).unwrap(), full);
+        assert_eq!(
+            Tag::from_str_with_max_len(format!("{full}a"), 100),
+            Err(TagFromStringError::LimitExceeded(Tag::MAX_LEN + 1))
+        );
+    }
+
+    #[test]
+    fn test_new_truncated() {
+        let exact = "a".repeat(Tag::MAX_LEN);
+        assert_eq!(Tag::new_truncated(&exact).unwrap(), exact);
+
+        let long = "a".repeat(Tag::MAX_LEN + 10);
+        assert_eq!(Tag::new_truncated(long).unwrap(), exact);
+
+        let long = format!("{}---b", "a".repeat(Tag::MAX_LEN - 2));
+        assert_eq!(
+            Tag::new_truncated(long).unwrap(),
+            "a".repeat(Tag::MAX_LEN - 2)
+        );
+
+        let long = format!("{}1b", "a".repeat(Tag::MAX_LEN - 1));
+        assert_eq!(
+            Tag::new_truncated(long),
+            Err(TagFromStringError::MustEndAlphanumeric(
+                '1',
+                Tag::MAX_LEN - 1
+            ))
+        );
+
+        let long = format!("a_{}", "b".repeat(Tag::MAX_LEN));
+        assert_eq!(
+            Tag::new_truncated(long),
+            Err(TagFromStringError::InvalidCharacter('_', 1))
+        );
+
+        let long = format!("{}é{}", "a".repeat(Tag::MAX_LEN - 1), "b".repeat(10));
+        assert_eq!(
+            Tag::new_truncated(long).unwrap(),
+            "a".repeat(Tag::MAX_LEN - 1)
+        );
+
+        let long = "-".repeat(Tag::MAX_LEN + 1);
+        assert_eq!(
+            Tag::new_truncated(long),
+            Err(TagFromStringError::MustStartAlphabetic('-', 0))
+        );
+
+        assert_eq!(Tag::new_truncated("").unwrap(), Tag::EMPTY);
+        assert_eq!(
+            Tag::new_truncated("foo-"),
+            Err(TagFromStringError::MustEndAlphanumeric('-', 3))
+        );
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(Tag::slugify("foo").unwrap(), "foo");
+        assert_eq!(Tag::slugify("Foo Bar").unwrap(), "foo-bar");
+        assert_eq!(Tag::slugify("--foo__bar--").unwrap(), "foo-bar");
+        assert_eq!(Tag::slugify("123 go").unwrap(), "go");
+        assert_eq!(Tag::slugify("Version 2").unwrap(), "version");
+        assert_eq!(Tag::slugify("a1b2").unwrap(), "a1b");
+        assert_eq!(Tag::slugify(""), None);
+        assert_eq!(Tag::slugify("--- 42 ---"), None);
+        assert_eq!(Tag::slugify("日本語"), None);
+
+        let long = "word ".repeat(20);
+        let slug = Tag::slugify(&long).unwrap();
+        assert_eq!(slug.len(), Tag::MAX_LEN);
+        assert!(slug.ends_with("-word-wor"));
+
+        let long = format!("{}-b", "a".repeat(Tag::MAX_LEN - 1));
+        assert_eq!(Tag::slugify(&long).unwrap(), "a".repeat(Tag::MAX_LEN - 1));
+    }
+
+    #[test]
+    fn test_normalize_cow() {
+        assert!(matches!(
+            Tag::normalize_cow("foo"),
+            Ok(Cow::Borrowed("foo"))
+        ));
+        assert!(matches!(
+            Tag::normalize_cow("\tfoo \n"),
+            Ok(Cow::Borrowed("foo"))
+        ));
+        assert!(matches!(Tag::normalize_cow("  "), Ok(Cow::Borrowed(""))));
+        assert_eq!(Tag::normalize_cow(" Foo-Bar ").unwrap(), "foo-bar");
+        assert!(matches!(Tag::normalize_cow("FOO"), Ok(Cow::Owned(_))));
+        assert_eq!(
+            Tag::normalize_cow(" foo- "),
+            Err(TagFromStringError::MustEndAlphanumeric('-', 3))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_de_trivial() {
+        assert_eq!(serde_json::from_str::<Tag>(r#""test""#).unwrap(), "test");
         assert_eq!(
             serde_json::from_str::<Tag>(r#""test-case""#).unwrap(),
             "test-case"
@@ -342,6 +2970,117 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_de_borrowed_and_escaped() {
+        assert_eq!(serde_json::from_slice::<Tag>(br#""test""#).unwrap(), "test");
+        assert_eq!(
+            serde_json::from_str::<Tag>(r#""te\u0073t""#).unwrap(),
+            "test"
+        );
+        assert!(serde_json::from_reader::<_, Tag>(&br#""a_b""#[..]).is_err());
+        assert!(serde_json::from_str::<Tag>("42").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_de_bytes() {
+        use serde::de::value::{BytesDeserializer, Error};
+
+        let de = BytesDeserializer::<Error>::new(b"foo-bar");
+        assert_eq!(Tag::deserialize(de).unwrap(), "foo-bar");
+        let tag = TagVisitor.visit_byte_buf::<Error>(b"foo-bar".to_vec());
+        assert_eq!(tag.unwrap(), "foo-bar");
+
+        let de = BytesDeserializer::<Error>::new(b"foo_bar");
+        assert!(Tag::deserialize(de).is_err());
+        let de = BytesDeserializer::<Error>::new(b"foo\xffbar");
+        assert!(Tag::deserialize(de).is_err());
+        let tag = TagVisitor.visit_byte_buf::<Error>(b"foo\xffbar".to_vec());
+        assert!(tag.is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_skip_invalid() {
+        let mut deserializer = serde_json::Deserializer::from_str(r#"["foo","","a_b","bar"]"#);
+        let tags = skip_invalid(&mut deserializer).unwrap();
+        assert_eq!(tags, [Tag::new("foo"), Tag::EMPTY, Tag::new("bar")]);
+
+        let mut deserializer = serde_json::Deserializer::from_str(r#"["foo",42]"#);
+        assert!(skip_invalid(&mut deserializer).is_err());
+
+        let tags = skip_invalid(serde_json::json!(["-", "baz"])).unwrap();
+        assert_eq!(tags, [Tag::new("baz")]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_transparent() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        #[serde(transparent)]
+        struct Wrapper(Tag);
+
+        let wrapper: Wrapper = serde_json::from_str(r#""foo-bar""#).unwrap();
+        assert_eq!(wrapper, Wrapper(Tag::new("foo-bar")));
+        assert_eq!(serde_json::to_string(&wrapper).unwrap(), r#""foo-bar""#);
+        assert!(serde_json::from_str::<Wrapper>(r#""foo-""#).is_err());
+        assert!(serde_json::from_str::<Wrapper>(r#"["foo"]"#).is_err());
+
+        let wrappers: Vec<Wrapper> = serde_json::from_str(r#"["foo","bar"]"#).unwrap();
+        assert_eq!(
+            wrappers,
+            [Wrapper(Tag::new("foo")), Wrapper(Tag::new("bar"))]
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "unsafe"))]
+    fn test_serde_raw() {
+        #[derive(Debug, Serialize, Deserialize)]
+        #[serde(transparent)]
+        struct Trusted(#[serde(with = "raw")] Tag);
+
+        let trusted: Trusted = serde_json::from_str(r#""foo-bar""#).unwrap();
+        assert_eq!(trusted.0, "foo-bar");
+        assert_eq!(serde_json::to_string(&trusted).unwrap(), r#""foo-bar""#);
+
+        let unchecked: Trusted = serde_json::from_str(r#""foo_""#).unwrap();
+        assert_eq!(unchecked.0, "foo_");
+        assert!(serde_json::from_str::<Trusted>("42").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_ser_error() {
+        let json = |e: TagFromStringError| serde_json::to_string(&e).unwrap();
+        assert_eq!(
+            json(TagFromStringError::MustStartAlphabetic('1', 0)),
+            r#"{"kind":"must_start_alphabetic","char":"1","position":0}"#
+        );
+        assert_eq!(
+            json(TagFromStringError::NonAscii('ö', 1)),
+            r#"{"kind":"non_ascii","char":"ö","position":1}"#
+        );
+        assert_eq!(
+            json(TagFromStringError::LimitExceeded(64)),
+            r#"{"kind":"limit_exceeded","length":64}"#
+        );
+        assert_eq!(
+            json(TagFromStringError::MaxLenExceeded(12, 10)),
+            r#"{"kind":"max_len_exceeded","length":12,"limit":10}"#
+        );
+        assert_eq!(
+            json(TagFromStringError::Confusable('\u{430}', 'a', 2)),
+            "{\"kind\":\"confusable\",\"char\":\"\u{430}\",\"position\":2,\"resembles\":\"a\"}"
+        );
+        assert_eq!(
+            json(TagFromStringError::ConsecutiveDash(2)),
+            r#"{"kind":"consecutive_dash","char":"-","position":2}"#
+        );
+        assert_eq!(json(TagFromStringError::Empty), r#"{"kind":"empty"}"#);
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn test_serde_ser_invalid() {
@@ -0,0 +1,113 @@
+//! Provides the [`ReservedTagSet`] type, a set of tags that must not be used.
+
+// SPDX-FileCopyrightText: Copyright 2023 Markus Mayer
+// SPDX-License-Identifier: EUPL-1.2
+// SPDX-FileType: SOURCE
+
+use crate::Tag;
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+/// A set of reserved tags, such as `default` or `system`, supporting constant-time
+/// membership checks during bulk validation.
+///
+/// For a handful of reserved tags, [`Tag::is_reserved`] may be sufficient.
+///
+/// ```
+/// use just_a_tag::{tag, ReservedTagSet, Tag};
+///
+/// let reserved = ReservedTagSet::from_iter([tag!("default"), tag!("system")]);
+/// assert!(reserved.contains(&Tag::new("system")));
+/// assert!(reserved.contains("default"));
+/// assert!(!reserved.contains("custom"));
+/// ```
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct ReservedTagSet(HashSet<Tag>);
+
+impl ReservedTagSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a reserved tag, returning `true` if it was not reserved before.
+    pub fn insert(&mut self, tag: Tag) -> bool {
+        self.0.insert(tag)
+    }
+
+    /// Adds a reserved tag, returning the set for chaining.
+    ///
+    /// ```
+    /// use just_a_tag::{tag, ReservedTagSet};
+    ///
+    /// let reserved = ReservedTagSet::new().with(tag!("internal"));
+    /// assert!(reserved.contains("internal"));
+    /// ```
+    pub fn with(mut self, tag: Tag) -> Self {
+        self.0.insert(tag);
+        self
+    }
+
+    /// Returns `true` if the value is reserved.
+    ///
+    /// Since tags dereference to `str`, this accepts both tags and string slices.
+    pub fn contains(&self, value: &str) -> bool {
+        self.0.contains(value)
+    }
+
+    /// Returns the number of reserved tags.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if no tags are reserved.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the reserved tags, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &Tag> {
+        self.0.iter()
+    }
+}
+
+impl FromIterator<Tag> for ReservedTagSet {
+    fn from_iter<T: IntoIterator<Item = Tag>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Extend<Tag> for ReservedTagSet {
+    fn extend<T: IntoIterator<Item = Tag>>(&mut self, iter: T) {
+        self.0.extend(iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains() {
+        let mut reserved = ReservedTagSet::new().with(Tag::new("default"));
+        assert!(reserved.insert(Tag::new("system")));
+        assert!(!reserved.insert(Tag::new("system")));
+        reserved.extend([Tag::new("internal")]);
+
+        assert_eq!(reserved.len(), 3);
+        assert!(reserved.contains("internal"));
+        assert!(reserved.contains(&Tag::new("default")));
+        assert!(!reserved.contains("systems"));
+        assert!(ReservedTagSet::new().is_empty());
+    }
+
+    #[test]
+    fn test_bulk_validation() {
+        let reserved = ReservedTagSet::from_iter([Tag::new("default"), Tag::new("system")]);
+        let rejected: Vec<_> = ["foo", "system", "bar", "default"]
+            .into_iter()
+            .filter(|value| reserved.contains(value))
+            .collect();
+        assert_eq!(rejected, ["system", "default"]);
+    }
+}
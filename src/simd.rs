@@ -0,0 +1,90 @@
+//! Provides a vectorized check of the tag character set.
+//!
+//! The check processes eight bytes at a time in a `u64` (SIMD within a register),
+//! which requires neither `unsafe` code nor platform specific intrinsics.
+
+// SPDX-FileCopyrightText: Copyright 2023 Markus Mayer
+// SPDX-License-Identifier: EUPL-1.2
+// SPDX-FileType: SOURCE
+
+/// A word with every byte set to `1`.
+const ONES: u64 = u64::from_ne_bytes([0x01; 8]);
+
+/// A word with the high bit of every byte set.
+const HIGH: u64 = u64::from_ne_bytes([0x80; 8]);
+
+/// Returns `true` if every byte is a lowercase ASCII letter, an ASCII digit or `-`.
+///
+/// The input must be ASCII.
+pub(crate) fn is_tag_charset(bytes: &[u8]) -> bool {
+    debug_assert!(bytes.is_ascii());
+
+    let mut chunks = bytes.chunks_exact(8);
+    for chunk in &mut chunks {
+        let word = u64::from_ne_bytes(chunk.try_into().expect("chunk has eight bytes"));
+        if !is_tag_charset_word(word) {
+            return false;
+        }
+    }
+
+    chunks
+        .remainder()
+        .iter()
+        .all(|&b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+}
+
+/// Checks the character set of eight ASCII bytes at once.
+#[inline(always)]
+fn is_tag_charset_word(word: u64) -> bool {
+    let valid =
+        in_range(word, b'a', b'z') | in_range(word, b'0', b'9') | in_range(word, b'-', b'-');
+    valid == HIGH
+}
+
+/// Sets the high bit of every byte within `lo..=hi`, clearing all other bits.
+///
+/// Since all bytes are below `0x80`, the additions cannot carry into the next byte.
+#[inline(always)]
+fn in_range(word: u64, lo: u8, hi: u8) -> u64 {
+    let at_least_lo = word + ONES * u64::from(0x80 - lo);
+    let above_hi = word + ONES * u64::from(0x7f - hi);
+    at_least_lo & !above_hi & HIGH
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_tag_charset_scalar(bytes: &[u8]) -> bool {
+        bytes
+            .iter()
+            .all(|&b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+    }
+
+    #[test]
+    fn test_every_ascii_byte() {
+        for b in 0..0x80u8 {
+            for position in 0..16 {
+                let mut bytes = [b'a'; 16];
+                bytes[position] = b;
+                assert_eq!(
+                    is_tag_charset(&bytes),
+                    is_tag_charset_scalar(&bytes),
+                    "byte {b:#04x} at {position}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_lengths() {
+        let valid = "abcdefghijklmnopqrstuvwxyz-0123456789".repeat(2);
+        for len in 0..valid.len() {
+            assert!(is_tag_charset(&valid.as_bytes()[..len]));
+
+            let mut invalid = valid.as_bytes()[..len].to_vec();
+            invalid.push(b'_');
+            assert!(!is_tag_charset(&invalid));
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! Provides the [`TagDisjunction`] type, a disjunction of tags.
+
+// SPDX-FileCopyrightText: Copyright 2023 Markus Mayer
+// SPDX-License-Identifier: EUPL-1.2
+// SPDX-FileType: SOURCE
+
+use crate::tag_union::{fmt_sorted, hash_sorted};
+use crate::{Tag, TagFromStringError};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// A tag disjunction, e.g. `foo` or `foo,bar,baz` (i.e. `foo` _or_ `bar` _or_ `baz`).
+///
+/// Where a [`TagUnion`](crate::TagUnion) requires all of its tags to be present,
+/// a disjunction requires any one of them.
+///
+/// ```
+/// use std::collections::HashSet;
+/// use just_a_tag::{Tag, TagDisjunction};
+///
+/// let disjunction = TagDisjunction::from_str("foo,bar").unwrap();
+/// assert!(disjunction.contains(&Tag::new("foo")));
+/// assert!(disjunction.contains(&Tag::new("bar")));
+/// assert_eq!(disjunction.len(), 2);
+///
+/// assert!(disjunction.matches_set(&HashSet::from_iter([Tag::new("bar")])));
+/// assert!(!disjunction.matches_set(&HashSet::from_iter([Tag::new("baz")])));
+/// ```
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct TagDisjunction(HashSet<Tag>);
+
+impl TagDisjunction {
+    /// Returns `true` if any tag of this disjunction is present in the set.
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use just_a_tag::{Tag, TagDisjunction};
+    ///
+    /// let disjunction = TagDisjunction::from_str("foo,bar").unwrap();
+    ///
+    /// // foo matches
+    /// let set_1 = HashSet::from_iter([Tag::new("foo"), Tag::new("baz")]);
+    /// assert!(disjunction.matches_set(&set_1));
+    ///
+    /// // none match
+    /// let set_2 = HashSet::from_iter([Tag::new("fubar"), Tag::new("baz")]);
+    /// assert!(!disjunction.matches_set(&set_2));
+    /// ```
+    pub fn matches_set(&self, values: &HashSet<Tag>) -> bool {
+        !self.0.is_disjoint(values)
+    }
+
+    /// Inserts a tag into this disjunction.
+    /// Returns whether the tag was inserted; that is:
+    ///
+    /// * If the tag was not previously inserted, `true` is returned,
+    /// * If the tag was previously inserted, `false` is returned.
+    pub fn insert(&mut self, tag: Tag) -> bool {
+        self.0.insert(tag)
+    }
+
+    /// Removes a tag from this disjunction.
+    /// Returns whether the tag was removed; that is:
+    ///
+    /// * If the tag was previously inserted, `true` is returned,
+    /// * If the tag was not previously inserted, `false` is returned.
+    pub fn remove<T: Borrow<Tag>>(&mut self, tag: T) -> bool {
+        self.0.remove(tag.borrow())
+    }
+
+    /// Returns whether this disjunction contains the specified tag. That is:
+    ///
+    /// * If the tag was previously inserted, `true` is returned,
+    /// * If the tag was not previously inserted, `false` is returned.
+    pub fn contains<T: Borrow<Tag>>(&self, tag: &T) -> bool {
+        self.0.contains(tag.borrow())
+    }
+
+    /// Attempts to parse a [`TagDisjunction`] from a string-like input.
+    ///
+    /// Error positions are byte offsets into the entire input.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str<S: AsRef<str>>(
+        value: S,
+    ) -> Result<TagDisjunction, TagDisjunctionFromStringError> {
+        let mut tags = HashSet::new();
+        let mut start = 0;
+        for name in value.as_ref().split(',') {
+            if !name.is_empty() {
+                tags.insert(Tag::from_str(name).map_err(|e| e.offset_by(start))?);
+            }
+            start += name.len() + 1;
+        }
+
+        Ok(Self(tags))
+    }
+}
+
+impl Deref for TagDisjunction {
+    type Target = HashSet<Tag>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Hash for TagDisjunction {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_sorted(&self.0, state)
+    }
+}
+
+impl FromIterator<Tag> for TagDisjunction {
+    fn from_iter<T: IntoIterator<Item = Tag>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Display for TagDisjunction {
+    /// Formats the disjunction as its canonical `a,b,c` form, with tags sorted lexicographically.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_sorted(&self.0, ",", f)
+    }
+}
+
+impl FromStr for TagDisjunction {
+    type Err = TagDisjunctionFromStringError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        TagDisjunction::from_str(value)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for TagDisjunction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let input = String::deserialize(deserializer)?;
+        match TagDisjunction::from_str(&input) {
+            Ok(tags) => Ok(tags),
+            Err(e) => Err(de::Error::custom(e)),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg(feature = "serde")]
+impl Serialize for TagDisjunction {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum TagDisjunctionFromStringError {
+    InvalidTag(TagFromStringError),
+}
+
+impl Display for TagDisjunctionFromStringError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TagDisjunctionFromStringError::InvalidTag(e) => write!(f, "Invalid tag: {e}"),
+        }
+    }
+}
+
+impl From<TagFromStringError> for TagDisjunctionFromStringError {
+    fn from(value: TagFromStringError) -> Self {
+        Self::InvalidTag(value)
+    }
+}
+
+impl Error for TagDisjunctionFromStringError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TagDisjunctionFromStringError::InvalidTag(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let tags = TagDisjunction::from_str("").unwrap();
+        assert!(tags.is_empty());
+        assert!(!tags.matches_set(&HashSet::from_iter([Tag::new("foo")])));
+    }
+
+    #[test]
+    fn test_parse() {
+        let tags = TagDisjunction::from_str("foo,bar,,,baz,").unwrap();
+        assert_eq!(tags.len(), 3);
+        assert!(tags.contains(&Tag::new("foo")));
+        assert!(tags.contains(&Tag::new("bar")));
+        assert!(tags.contains(&Tag::new("baz")));
+        assert_eq!(tags.to_string(), "bar,baz,foo");
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(
+            TagDisjunction::from_str("foo,#baz"),
+            Err(TagDisjunctionFromStringError::InvalidTag(
+                TagFromStringError::MustStartAlphabetic('#', 4)
+            ))
+        );
+        assert!(TagDisjunction::from_str("foo+bar").is_err());
+    }
+
+    #[test]
+    fn test_error_source() {
+        let error = TagDisjunction::from_str("foo,a_b").unwrap_err();
+        assert_eq!(
+            error.source().unwrap().downcast_ref::<TagFromStringError>(),
+            Some(&TagFromStringError::InvalidCharacter('_', 5))
+        );
+    }
+
+    #[test]
+    fn test_hash_ignores_order() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let hash = |tags: &TagDisjunction| {
+            let mut hasher = DefaultHasher::new();
+            tags.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let a = TagDisjunction::from_str("foo,bar,baz").unwrap();
+        let b = TagDisjunction::from_str("baz,foo,bar").unwrap();
+        assert_eq!(hash(&a), hash(&b));
+    }
+
+    #[test]
+    fn test_matches() {
+        let tags = TagDisjunction::from_str("foo,bar").unwrap();
+        assert!(tags.matches_set(&HashSet::from_iter([Tag::new("foo")])));
+        assert!(tags.matches_set(&HashSet::from_iter([Tag::new("bar"), Tag::new("baz")])));
+        assert!(!tags.matches_set(&HashSet::from_iter([Tag::new("baz")])));
+        assert!(!tags.matches_set(&HashSet::new()));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_roundtrip() {
+        let tags = TagDisjunction::from_str("foo,bar").unwrap();
+        let json = serde_json::to_string(&tags).unwrap();
+        assert_eq!(json, r#""bar,foo""#);
+        assert_eq!(serde_json::from_str::<TagDisjunction>(&json).unwrap(), tags);
+    }
+}
@@ -0,0 +1,262 @@
+//! Provides the [`TagExpression`] type, a disjunction of tag unions.
+
+// SPDX-FileCopyrightText: Copyright 2023 Markus Mayer
+// SPDX-License-Identifier: EUPL-1.2
+// SPDX-FileType: SOURCE
+
+use crate::{MatchesAnyTagUnion, Tag, TagUnion, TagUnionFromStringError};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A tag expression, e.g. `foo+bar,baz` (i.e. (`foo` _and_ `bar`) _or_ `baz`).
+///
+/// Expressions consist of `,`-separated [`TagUnion`]s, any of which needs to match.
+///
+/// ```
+/// use std::collections::HashSet;
+/// use just_a_tag::{Tag, TagExpression};
+///
+/// let expression = TagExpression::from_str("foo+bar,baz").unwrap();
+/// assert_eq!(expression.unions().len(), 2);
+///
+/// // foo+bar matches
+/// assert!(expression.matches_set(&HashSet::from_iter([Tag::new("foo"), Tag::new("bar")])));
+///
+/// // baz matches
+/// assert!(expression.matches_set(&HashSet::from_iter([Tag::new("baz")])));
+///
+/// // none match
+/// assert!(!expression.matches_set(&HashSet::from_iter([Tag::new("foo")])));
+/// ```
+///
+/// Expressions can be built from, and converted back into, a list of non-empty unions:
+///
+/// ```
+/// use just_a_tag::{TagExpression, TagUnion};
+///
+/// let unions = vec![TagUnion::from_str("foo+bar").unwrap(), TagUnion::from_str("baz").unwrap()];
+/// let expression = TagExpression::try_from(unions.clone()).unwrap();
+/// assert_eq!(expression.to_string(), "bar+foo,baz");
+/// assert_eq!(Vec::from(expression), unions);
+/// ```
+#[doc(alias = "TagUnionList")]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct TagExpression(Vec<TagUnion>);
+
+/// A list of [`TagUnion`]s, any of which needs to match.
+///
+/// This is the same type as [`TagExpression`], which already stores its unions
+/// as a list and formats them in the `foo+bar,baz` form.
+pub type TagUnionList = TagExpression;
+
+impl TagExpression {
+    /// Returns `true` if any union of this expression matches the value presented in the set.
+    pub fn matches_set(&self, values: &HashSet<Tag>) -> bool {
+        self.0.matches_set(values)
+    }
+
+    /// Returns the unions of this expression.
+    pub fn unions(&self) -> &[TagUnion] {
+        &self.0
+    }
+
+    /// Attempts to parse a [`TagExpression`] from a string-like input.
+    ///
+    /// Error positions are byte offsets into the entire input.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str<S: AsRef<str>>(value: S) -> Result<TagExpression, TagUnionFromStringError> {
+        let mut unions = Vec::new();
+        let mut start = 0;
+        for group in value.as_ref().split(',') {
+            let union = TagUnion::parse_at(group, '+', start)?;
+            start += group.len() + 1;
+            if !union.is_empty() {
+                unions.push(union);
+            }
+        }
+
+        Ok(Self(unions))
+    }
+}
+
+impl TryFrom<Vec<TagUnion>> for TagExpression {
+    type Error = EmptyTagUnionError;
+
+    /// Wraps the unions into an expression, rejecting empty unions.
+    ///
+    /// An empty union would match every set, and could not be represented in the
+    /// expression's string form.
+    fn try_from(unions: Vec<TagUnion>) -> Result<Self, Self::Error> {
+        match unions.iter().position(|union| union.is_empty()) {
+            Some(index) => Err(EmptyTagUnionError(index)),
+            None => Ok(Self(unions)),
+        }
+    }
+}
+
+impl From<TagExpression> for Vec<TagUnion> {
+    fn from(expression: TagExpression) -> Self {
+        expression.0
+    }
+}
+
+impl Display for TagExpression {
+    /// Formats the expression as `,`-separated unions in their canonical form.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, union) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{union}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TagExpression {
+    type Err = TagUnionFromStringError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        TagExpression::from_str(value)
+    }
+}
+
+/// The error returned when building a [`TagExpression`] from a list containing an
+/// empty [`TagUnion`]; holds the index of the first empty union.
+#[derive(Debug, Eq, PartialEq)]
+pub struct EmptyTagUnionError(pub usize);
+
+impl Display for EmptyTagUnionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Empty tag union at index {}", self.0)
+    }
+}
+
+impl Error for EmptyTagUnionError {}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for TagExpression {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let input = String::deserialize(deserializer)?;
+        TagExpression::from_str(input).map_err(de::Error::custom)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg(feature = "serde")]
+impl Serialize for TagExpression {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TagFromStringError;
+
+    #[test]
+    fn test_empty() {
+        let expression = TagExpression::from_str("").unwrap();
+        assert!(expression.unions().is_empty());
+        assert!(!expression.matches_set(&HashSet::from_iter([Tag::new("foo")])));
+    }
+
+    #[test]
+    fn test_parse() {
+        let expression = TagExpression::from_str("foo+bar,,baz,").unwrap();
+        assert_eq!(
+            expression.unions(),
+            [
+                TagUnion::from_str("foo+bar").unwrap(),
+                TagUnion::from_str("baz").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(
+            TagExpression::from_str("foo,bar+#baz"),
+            Err(TagUnionFromStringError::InvalidTag(
+                TagFromStringError::MustStartAlphabetic('#', 8)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        let expression = TagExpression::from_str("foo+bar,baz,qux+quux").unwrap();
+        assert_eq!(expression.to_string(), "bar+foo,baz,quux+qux");
+        assert_eq!(
+            TagExpression::from_str(expression.to_string()).unwrap(),
+            expression
+        );
+    }
+
+    #[test]
+    fn test_try_from_unions() {
+        let unions = vec![
+            TagUnion::from_str("foo").unwrap(),
+            TagUnion::from_str("bar+baz").unwrap(),
+        ];
+
+        let expression = TagExpression::try_from(unions.clone()).unwrap();
+        assert_eq!(expression.to_string(), "foo,bar+baz");
+        assert_eq!(
+            TagExpression::from_str(expression.to_string()).unwrap(),
+            expression
+        );
+        assert_eq!(Vec::from(expression), unions);
+
+        let list: TagUnionList = TagExpression::try_from(Vec::new()).unwrap();
+        assert!(list.unions().is_empty());
+    }
+
+    #[test]
+    fn test_try_from_unions_rejects_empty() {
+        let unions = vec![
+            TagUnion::from_str("foo").unwrap(),
+            TagUnion::default(),
+            TagUnion::from_str("bar").unwrap(),
+        ];
+
+        assert_eq!(TagExpression::try_from(unions), Err(EmptyTagUnionError(1)));
+        assert_eq!(
+            EmptyTagUnionError(1).to_string(),
+            "Empty tag union at index 1"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_roundtrip() {
+        let expression = TagExpression::from_str("foo+bar,baz").unwrap();
+        let json = serde_json::to_string(&expression).unwrap();
+        assert_eq!(json, r#""bar+foo,baz""#);
+        assert_eq!(
+            serde_json::from_str::<TagExpression>(&json).unwrap(),
+            expression
+        );
+        assert!(serde_json::from_str::<TagExpression>(r#""foo,bar-""#).is_err());
+    }
+
+    #[test]
+    fn test_matches() {
+        let expression = TagExpression::from_str("foo+bar,baz").unwrap();
+        assert!(expression.matches_set(&HashSet::from_iter([Tag::new("foo"), Tag::new("bar")])));
+        assert!(expression.matches_set(&HashSet::from_iter([Tag::new("baz")])));
+        assert!(!expression.matches_set(&HashSet::from_iter([Tag::new("foo"), Tag::new("bang")])));
+    }
+}
@@ -0,0 +1,128 @@
+//! Provides the [`TagInterner`] type, deduplicating the storage of equal tags.
+
+// SPDX-FileCopyrightText: Copyright 2023 Markus Mayer
+// SPDX-License-Identifier: EUPL-1.2
+// SPDX-FileType: SOURCE
+
+use crate::tag_str::TagStr;
+use crate::{Tag, TagFromStringError};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Hands out tags sharing a single allocation per distinct value.
+///
+/// Interned tags behave exactly like any other [`Tag`]; cloning them only
+/// increments a reference count. This pays off when the same tags are held
+/// in many places, e.g. `env-prod` across thousands of sets.
+///
+/// ## Memory
+///
+/// The interner keeps every distinct value alive until it is [cleared](Self::clear)
+/// or dropped, even if no tag refers to it anymore. Tags handed out remain valid
+/// after the interner is gone. Interning values that rarely repeat only adds overhead.
+///
+/// ## Thread safety
+///
+/// Interned tags are [`Send`] and [`Sync`]. Interning requires `&mut self`, so
+/// an interner shared between threads must be wrapped, e.g. in a [`Mutex`](std::sync::Mutex).
+///
+/// ```
+/// use just_a_tag::TagInterner;
+///
+/// let mut interner = TagInterner::new();
+/// let a = interner.intern("env-prod").unwrap();
+/// let b = interner.intern("env-prod").unwrap();
+/// assert_eq!(a, b);
+/// assert_eq!(interner.len(), 1);
+///
+/// assert!(interner.intern("invalid-").is_err());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct TagInterner {
+    values: HashSet<Arc<str>>,
+}
+
+impl TagInterner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a tag for the value, sharing the storage of previously interned equal values.
+    ///
+    /// The value is validated like [`Tag::from_str`](Tag::from_str); invalid values
+    /// are not interned.
+    pub fn intern(&mut self, value: &str) -> Result<Tag, TagFromStringError> {
+        if value.is_empty() {
+            return Ok(Tag::EMPTY.clone());
+        }
+
+        if let Some(shared) = self.values.get(value) {
+            return Ok(Tag(TagStr::Shared(shared.clone())));
+        }
+
+        Tag::validate(value)?;
+        let shared: Arc<str> = Arc::from(value);
+        self.values.insert(shared.clone());
+        Ok(Tag(TagStr::Shared(shared)))
+    }
+
+    /// Returns the number of distinct values held by the interner.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the interner holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Releases all values held by the interner.
+    ///
+    /// Previously interned tags remain valid, but are no longer shared with
+    /// tags interned afterwards.
+    pub fn clear(&mut self) {
+        self.values.clear()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern_shares_storage() {
+        let mut interner = TagInterner::new();
+        let a = interner.intern("foo").unwrap();
+        let b = interner.intern("foo").unwrap();
+        let c = interner.intern("bar").unwrap();
+
+        assert_eq!(a, b);
+        assert_eq!(a, "foo");
+        assert_ne!(a, c);
+        assert_eq!(a.as_str().as_ptr(), b.as_str().as_ptr());
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn test_intern_invalid() {
+        let mut interner = TagInterner::new();
+        assert_eq!(
+            interner.intern("foo_bar"),
+            Err(TagFromStringError::InvalidCharacter('_', 3))
+        );
+        assert_eq!(interner.intern("").unwrap(), Tag::EMPTY);
+        assert!(interner.is_empty());
+    }
+
+    #[test]
+    fn test_outlives_interner() {
+        let mut interner = TagInterner::new();
+        let tag = interner.intern("foo").unwrap();
+        interner.clear();
+        drop(interner);
+
+        assert_eq!(tag, Tag::new("foo"));
+        assert_eq!(tag.into_inner(), "foo");
+    }
+}
@@ -0,0 +1,269 @@
+//! Provides the [`TagPattern`] type, a union of tags supporting trailing wildcards.
+
+// SPDX-FileCopyrightText: Copyright 2023 Markus Mayer
+// SPDX-License-Identifier: EUPL-1.2
+// SPDX-FileType: SOURCE
+
+use crate::{Tag, TagFromStringError};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A tag pattern, e.g. `env-*+tier-web` (i.e. any tag starting with `env-` _and_ `tier-web`).
+///
+/// Patterns are unions whose members may end in a `*` wildcard, which matches any
+/// tag starting with the part before the wildcard. Wildcards are only supported
+/// at the end of a member.
+///
+/// ```
+/// use std::collections::HashSet;
+/// use just_a_tag::{Tag, TagPattern};
+///
+/// let pattern = TagPattern::from_str("env-*+tier-web").unwrap();
+///
+/// let set_1 = HashSet::from_iter([Tag::new("env-prod"), Tag::new("tier-web")]);
+/// assert!(pattern.matches_set(&set_1));
+///
+/// let set_2 = HashSet::from_iter([Tag::new("environment"), Tag::new("tier-web")]);
+/// assert!(!pattern.matches_set(&set_2));
+///
+/// assert!(TagPattern::from_str("env-*-prod").is_err());
+/// ```
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct TagPattern {
+    /// The tags that must be present.
+    exact: HashSet<Tag>,
+    /// The prefixes of which a matching tag must be present.
+    prefixes: Vec<String>,
+}
+
+impl TagPattern {
+    /// Returns `true` if every member of this pattern matches a tag in the set.
+    pub fn matches_set(&self, values: &HashSet<Tag>) -> bool {
+        self.exact.is_subset(values)
+            && self
+                .prefixes
+                .iter()
+                .all(|prefix| values.iter().any(|tag| tag.starts_with(prefix.as_str())))
+    }
+
+    /// Attempts to parse a [`TagPattern`] from a string-like input.
+    ///
+    /// Error positions are byte offsets into the entire input.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str<S: AsRef<str>>(value: S) -> Result<TagPattern, TagPatternFromStringError> {
+        let mut pattern = TagPattern::default();
+        let mut offset = 0;
+        for part in value.as_ref().split('+') {
+            let start = offset;
+            offset += part.len() + 1;
+            if part.is_empty() {
+                continue;
+            }
+
+            match part.find('*') {
+                None => {
+                    let tag = Tag::from_str(part).map_err(|e| e.offset_by(start))?;
+                    pattern.exact.insert(tag);
+                }
+                Some(position) if position == part.len() - 1 => {
+                    let prefix = &part[..position];
+                    Self::validate_prefix(prefix).map_err(|e| e.offset_by(start))?;
+                    if !pattern.prefixes.iter().any(|p| p == prefix) {
+                        pattern.prefixes.push(prefix.into());
+                    }
+                }
+                Some(position) => {
+                    return Err(TagPatternFromStringError::InvalidWildcard(start + position))
+                }
+            }
+        }
+
+        pattern.prefixes.sort();
+        Ok(pattern)
+    }
+
+    /// Validates the part of a member before the wildcard.
+    ///
+    /// Unlike a tag, the prefix may be empty and may end with `-`.
+    fn validate_prefix(prefix: &str) -> Result<(), TagFromStringError> {
+        if prefix.len() > Tag::MAX_LEN {
+            return Err(TagFromStringError::LimitExceeded(prefix.len()));
+        }
+
+        for (position, c) in prefix.char_indices() {
+            if position == 0 && !c.is_ascii_lowercase() {
+                return Err(TagFromStringError::MustStartAlphabetic(c, 0));
+            }
+            if !c.is_ascii_digit() && !c.is_ascii_lowercase() && c != '-' {
+                return Err(TagFromStringError::InvalidCharacter(c, position));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for TagPattern {
+    /// Formats the pattern in a canonical form, with exact tags sorted first, followed by
+    /// the sorted wildcard members.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut exact = Vec::from_iter(self.exact.iter());
+        exact.sort();
+
+        let members = exact
+            .into_iter()
+            .map(|tag| (tag.as_str(), ""))
+            .chain(self.prefixes.iter().map(|prefix| (prefix.as_str(), "*")));
+        for (i, (value, wildcard)) in members.enumerate() {
+            if i > 0 {
+                f.write_str("+")?;
+            }
+            write!(f, "{value}{wildcard}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TagPattern {
+    type Err = TagPatternFromStringError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        TagPattern::from_str(value)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum TagPatternFromStringError {
+    InvalidTag(TagFromStringError),
+    InvalidWildcard(usize),
+}
+
+impl Display for TagPatternFromStringError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TagPatternFromStringError::InvalidTag(e) => write!(f, "Invalid tag: {e}"),
+            TagPatternFromStringError::InvalidWildcard(pos) => write!(
+                f,
+                "Wildcards are only allowed at the end of a tag, got '*' at position {pos}"
+            ),
+        }
+    }
+}
+
+impl From<TagFromStringError> for TagPatternFromStringError {
+    fn from(value: TagFromStringError) -> Self {
+        Self::InvalidTag(value)
+    }
+}
+
+impl Error for TagPatternFromStringError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TagPatternFromStringError::InvalidTag(e) => Some(e),
+            TagPatternFromStringError::InvalidWildcard(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let pattern = TagPattern::from_str("").unwrap();
+        assert!(pattern.matches_set(&HashSet::new()));
+    }
+
+    #[test]
+    fn test_matches() {
+        let pattern = TagPattern::from_str("env-*+tier-web").unwrap();
+        assert!(pattern.matches_set(&HashSet::from_iter([
+            Tag::new("env-prod"),
+            Tag::new("tier-web")
+        ])));
+        assert!(!pattern.matches_set(&HashSet::from_iter([Tag::new("env-prod")])));
+        assert!(!pattern.matches_set(&HashSet::from_iter([Tag::new("tier-web")])));
+        assert!(!pattern.matches_set(&HashSet::from_iter([
+            Tag::new("environment"),
+            Tag::new("tier-web")
+        ])));
+    }
+
+    #[test]
+    fn test_wildcard_only() {
+        let pattern = TagPattern::from_str("*").unwrap();
+        assert!(pattern.matches_set(&HashSet::from_iter([Tag::new("foo")])));
+        assert!(!pattern.matches_set(&HashSet::new()));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(
+            TagPattern::from_str("foo+env-*-prod"),
+            Err(TagPatternFromStringError::InvalidWildcard(8))
+        );
+        assert_eq!(
+            TagPattern::from_str("*env"),
+            Err(TagPatternFromStringError::InvalidWildcard(0))
+        );
+        assert_eq!(
+            TagPattern::from_str("env_*"),
+            Err(TagPatternFromStringError::InvalidTag(
+                TagFromStringError::InvalidCharacter('_', 3)
+            ))
+        );
+        assert_eq!(
+            TagPattern::from_str("foo++env_*"),
+            Err(TagPatternFromStringError::InvalidTag(
+                TagFromStringError::InvalidCharacter('_', 8)
+            ))
+        );
+        assert_eq!(
+            TagPattern::from_str("env-*+tier_web"),
+            Err(TagPatternFromStringError::InvalidTag(
+                TagFromStringError::InvalidCharacter('_', 10)
+            ))
+        );
+        assert_eq!(
+            TagPattern::from_str("-*"),
+            Err(TagPatternFromStringError::InvalidTag(
+                TagFromStringError::MustStartAlphabetic('-', 0)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_error_source() {
+        let error = TagPattern::from_str("env_*").unwrap_err();
+        assert_eq!(
+            error.source().unwrap().downcast_ref::<TagFromStringError>(),
+            Some(&TagFromStringError::InvalidCharacter('_', 3))
+        );
+        assert!(TagPattern::from_str("*env").unwrap_err().source().is_none());
+    }
+
+    #[test]
+    fn test_prefix_length() {
+        let prefix = "a".repeat(Tag::MAX_LEN);
+        let pattern = TagPattern::from_str(format!("{prefix}*")).unwrap();
+        assert!(pattern.matches_set(&HashSet::from_iter([Tag::new(&prefix)])));
+
+        let prefix = "a".repeat(Tag::MAX_LEN + 1);
+        assert_eq!(
+            TagPattern::from_str(format!("{prefix}*")),
+            Err(TagPatternFromStringError::InvalidTag(
+                TagFromStringError::LimitExceeded(Tag::MAX_LEN + 1)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        let pattern = TagPattern::from_str("tier-web+env-*+app+env-*").unwrap();
+        assert_eq!(pattern.to_string(), "app+tier-web+env-*");
+        assert_eq!(TagPattern::from_str(pattern.to_string()).unwrap(), pattern);
+    }
+}
@@ -0,0 +1,311 @@
+//! Provides the [`TagPolicy`] type, combining allowed and denied tag unions.
+
+// SPDX-FileCopyrightText: Copyright 2023 Markus Mayer
+// SPDX-License-Identifier: EUPL-1.2
+// SPDX-FileType: SOURCE
+
+use crate::{MatchesAnyTagUnion, Tag, TagUnion, TagUnionFromStringError};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// An access policy, e.g. `foo+bar,baz;!tmp+old` (i.e. (`foo` _and_ `bar`) _or_ `baz`,
+/// but _not_ (`tmp` _and_ `old`)).
+///
+/// A set passes the policy if it matches any of the allowed unions and none
+/// of the denied unions. A policy without allowed unions never passes.
+///
+/// When parsing, the allowed unions are given as a `,`-separated list, optionally
+/// followed by a `;` and the `,`-separated denied unions prefixed by `!`.
+///
+/// ```
+/// use std::collections::HashSet;
+/// use just_a_tag::{Tag, TagPolicy};
+///
+/// let policy = TagPolicy::from_str("foo+bar,baz;!tmp+old").unwrap();
+///
+/// // foo+bar is allowed
+/// assert!(policy.evaluate(&HashSet::from_iter([Tag::new("foo"), Tag::new("bar")])));
+///
+/// // baz is allowed, but tmp+old is denied
+/// assert!(!policy.evaluate(&HashSet::from_iter([Tag::new("baz"), Tag::new("tmp"), Tag::new("old")])));
+///
+/// // nothing is allowed
+/// assert!(!policy.evaluate(&HashSet::from_iter([Tag::new("tmp")])));
+/// ```
+///
+/// Policies can also be composed from unions directly:
+///
+/// ```
+/// use std::collections::HashSet;
+/// use just_a_tag::{Tag, TagPolicy, TagUnion};
+///
+/// let policy = TagPolicy::new()
+///     .allowing(TagUnion::from_str("env-prod").unwrap())
+///     .denying(TagUnion::from_str("deprecated").unwrap());
+///
+/// assert_eq!(policy.to_string(), "env-prod;!deprecated");
+/// assert!(policy.evaluate(&HashSet::from_iter([Tag::new("env-prod")])));
+/// ```
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct TagPolicy {
+    /// The unions of which any must match.
+    allow: Vec<TagUnion>,
+    /// The unions of which none may match.
+    deny: Vec<TagUnion>,
+}
+
+impl TagPolicy {
+    /// Creates an empty policy, which denies every set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a policy from allowed and denied unions.
+    pub fn from_unions(allow: Vec<TagUnion>, deny: Vec<TagUnion>) -> Self {
+        Self { allow, deny }
+    }
+
+    /// Adds an allowed union, returning the policy for chaining.
+    pub fn allowing(mut self, union: TagUnion) -> Self {
+        self.allow.push(union);
+        self
+    }
+
+    /// Adds a denied union, returning the policy for chaining.
+    pub fn denying(mut self, union: TagUnion) -> Self {
+        self.deny.push(union);
+        self
+    }
+
+    /// Returns the allowed unions of this policy.
+    pub fn allow(&self) -> &[TagUnion] {
+        &self.allow
+    }
+
+    /// Returns the denied unions of this policy.
+    pub fn deny(&self) -> &[TagUnion] {
+        &self.deny
+    }
+
+    /// Returns `true` if the set matches any allowed union and no denied union.
+    pub fn evaluate(&self, set: &HashSet<Tag>) -> bool {
+        self.allow.matches_set(set) && self.deny.matches_none(set)
+    }
+
+    /// Attempts to parse a [`TagPolicy`] from a string-like input.
+    ///
+    /// Error positions are byte offsets into the entire input.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str<S: AsRef<str>>(value: S) -> Result<TagPolicy, TagPolicyFromStringError> {
+        let value = value.as_ref();
+        let (allow, deny) = match value.split_once(';') {
+            Some((allow, deny)) => (allow, Some(deny)),
+            None => (value, None),
+        };
+
+        let mut policy = Self::from_unions(parse_unions(allow, 0)?, Vec::new());
+        if let Some(deny) = deny {
+            let deny = deny
+                .strip_prefix('!')
+                .ok_or(TagPolicyFromStringError::MissingNegation)?;
+            policy.deny = parse_unions(deny, allow.len() + 2)?;
+        }
+
+        Ok(policy)
+    }
+}
+
+/// Parses `,`-separated unions located at `offset` within the policy, skipping empty ones.
+fn parse_unions(value: &str, offset: usize) -> Result<Vec<TagUnion>, TagUnionFromStringError> {
+    let mut unions = Vec::new();
+    let mut start = offset;
+    for group in value.split(',') {
+        let union = TagUnion::parse_at(group, '+', start)?;
+        start += group.len() + 1;
+        if !union.is_empty() {
+            unions.push(union);
+        }
+    }
+
+    Ok(unions)
+}
+
+impl Display for TagPolicy {
+    /// Formats the policy with its unions in their canonical form, omitting
+    /// the denied part if there are no denied unions.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let write_unions = |f: &mut Formatter<'_>, unions: &[TagUnion]| {
+            for (i, union) in unions.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                write!(f, "{union}")?;
+            }
+            Ok(())
+        };
+
+        write_unions(f, &self.allow)?;
+        if !self.deny.is_empty() {
+            f.write_str(";!")?;
+            write_unions(f, &self.deny)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TagPolicy {
+    type Err = TagPolicyFromStringError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        TagPolicy::from_str(value)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum TagPolicyFromStringError {
+    InvalidUnion(TagUnionFromStringError),
+    MissingNegation,
+}
+
+impl Display for TagPolicyFromStringError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TagPolicyFromStringError::InvalidUnion(e) => write!(f, "Invalid union: {e}"),
+            TagPolicyFromStringError::MissingNegation => {
+                write!(f, "Denied unions must be prefixed with '!'")
+            }
+        }
+    }
+}
+
+impl From<TagUnionFromStringError> for TagPolicyFromStringError {
+    fn from(value: TagUnionFromStringError) -> Self {
+        Self::InvalidUnion(value)
+    }
+}
+
+impl Error for TagPolicyFromStringError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TagPolicyFromStringError::InvalidUnion(e) => Some(e),
+            TagPolicyFromStringError::MissingNegation => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TagFromStringError;
+
+    fn set(tags: &[&str]) -> HashSet<Tag> {
+        tags.iter().map(Tag::new).collect()
+    }
+
+    #[test]
+    fn test_empty() {
+        let policy = TagPolicy::from_str("").unwrap();
+        assert_eq!(policy, TagPolicy::new());
+        assert!(!policy.evaluate(&set(&["foo"])));
+        assert!(!policy.evaluate(&HashSet::new()));
+    }
+
+    #[test]
+    fn test_parse() {
+        let policy = TagPolicy::from_str("foo+bar,baz;!tmp+old,debug").unwrap();
+        assert_eq!(
+            policy.allow(),
+            [
+                TagUnion::from_str("foo+bar").unwrap(),
+                TagUnion::from_str("baz").unwrap()
+            ]
+        );
+        assert_eq!(
+            policy.deny(),
+            [
+                TagUnion::from_str("tmp+old").unwrap(),
+                TagUnion::from_str("debug").unwrap()
+            ]
+        );
+        assert!(TagPolicy::from_str("foo").unwrap().deny().is_empty());
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(
+            TagPolicy::from_str("foo;tmp"),
+            Err(TagPolicyFromStringError::MissingNegation)
+        );
+        assert_eq!(
+            TagPolicy::from_str("foo;!tmp-"),
+            Err(TagPolicyFromStringError::InvalidUnion(
+                TagUnionFromStringError::InvalidTag(TagFromStringError::MustEndAlphanumeric(
+                    '-', 8
+                ))
+            ))
+        );
+        assert_eq!(
+            TagPolicy::from_str("foo;!tmp;!old"),
+            Err(TagPolicyFromStringError::InvalidUnion(
+                TagUnionFromStringError::InvalidTag(TagFromStringError::InvalidCharacter(';', 8))
+            ))
+        );
+        assert_eq!(
+            TagPolicy::from_str("foo+bar,baz+q_x"),
+            Err(TagPolicyFromStringError::InvalidUnion(
+                TagUnionFromStringError::InvalidTag(TagFromStringError::InvalidCharacter('_', 13))
+            ))
+        );
+        assert_eq!(
+            TagPolicy::from_str("foo,,#bar"),
+            Err(TagPolicyFromStringError::InvalidUnion(
+                TagUnionFromStringError::InvalidTag(TagFromStringError::MustStartAlphabetic(
+                    '#', 5
+                ))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_error_source() {
+        let error = TagPolicy::from_str("foo;!a_b").unwrap_err();
+        let source = error.source().unwrap();
+        assert_eq!(
+            source.downcast_ref::<TagUnionFromStringError>(),
+            Some(&TagUnionFromStringError::InvalidTag(
+                TagFromStringError::InvalidCharacter('_', 6)
+            ))
+        );
+        assert_eq!(
+            source
+                .source()
+                .unwrap()
+                .downcast_ref::<TagFromStringError>(),
+            Some(&TagFromStringError::InvalidCharacter('_', 6))
+        );
+        assert!(TagPolicy::from_str("foo;bar")
+            .unwrap_err()
+            .source()
+            .is_none());
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let policy = TagPolicy::from_str("foo+bar,baz;!tmp+old").unwrap();
+        assert!(policy.evaluate(&set(&["foo", "bar"])));
+        assert!(policy.evaluate(&set(&["baz", "tmp"])));
+        assert!(!policy.evaluate(&set(&["baz", "tmp", "old"])));
+        assert!(!policy.evaluate(&set(&["foo", "tmp"])));
+        assert!(!policy.evaluate(&HashSet::new()));
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        let policy = TagPolicy::from_str("foo+bar,baz;!tmp+old").unwrap();
+        assert_eq!(policy.to_string(), "bar+foo,baz;!old+tmp");
+        assert_eq!(TagPolicy::from_str(policy.to_string()).unwrap(), policy);
+        assert_eq!(TagPolicy::from_str("foo").unwrap().to_string(), "foo");
+    }
+}
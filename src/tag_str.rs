@@ -0,0 +1,137 @@
+//! Provides the [`TagStr`] type, the storage backing a [`Tag`](crate::Tag).
+
+// SPDX-FileCopyrightText: Copyright 2023 Markus Mayer
+// SPDX-License-Identifier: EUPL-1.2
+// SPDX-FileType: SOURCE
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "intern")]
+use alloc::sync::Arc;
+use core::cmp::Ordering;
+use core::fmt::{Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+/// The owned string type. With the `smartstring` feature, short values are
+/// stored inline instead of on the heap.
+#[cfg(all(feature = "alloc", not(feature = "smartstring")))]
+type OwnedStr = String;
+
+/// The owned string type. With the `smartstring` feature, short values are
+/// stored inline instead of on the heap.
+#[cfg(feature = "smartstring")]
+type OwnedStr = smartstring::alias::String;
+
+/// The storage of a tag's value.
+///
+/// Equality, ordering and hashing are those of the wrapped `str`.
+#[derive(Clone)]
+pub(crate) enum TagStr {
+    /// A static string, which never allocates.
+    Static(&'static str),
+    /// An owned string.
+    #[cfg(feature = "alloc")]
+    Owned(OwnedStr),
+    /// A string shared between tags, e.g. by a [`TagInterner`](crate::TagInterner).
+    #[cfg(feature = "intern")]
+    Shared(Arc<str>),
+}
+
+impl TagStr {
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        match self {
+            TagStr::Static(value) => value,
+            #[cfg(feature = "alloc")]
+            TagStr::Owned(value) => value,
+            #[cfg(feature = "intern")]
+            TagStr::Shared(value) => value,
+        }
+    }
+
+    /// Returns the first `len` bytes of the value, borrowing static strings
+    /// instead of copying them.
+    #[cfg(feature = "alloc")]
+    pub fn prefix(&self, len: usize) -> TagStr {
+        match self {
+            TagStr::Static(value) => TagStr::Static(&value[..len]),
+            _ => TagStr::from(&self.as_str()[..len]),
+        }
+    }
+
+    /// Consumes the value and returns it as a [`String`].
+    #[cfg(feature = "alloc")]
+    #[inline]
+    #[allow(clippy::useless_conversion)] // not a no-op with the `smartstring` feature
+    pub fn into_string(self) -> String {
+        match self {
+            TagStr::Static(value) => value.into(),
+            TagStr::Owned(value) => value.into(),
+            #[cfg(feature = "intern")]
+            TagStr::Shared(value) => value.as_ref().into(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<&str> for TagStr {
+    #[inline]
+    fn from(value: &str) -> Self {
+        TagStr::Owned(value.into())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<String> for TagStr {
+    #[inline]
+    #[allow(clippy::useless_conversion)] // not a no-op with the `smartstring` feature
+    fn from(value: String) -> Self {
+        TagStr::Owned(value.into())
+    }
+}
+
+impl Deref for TagStr {
+    type Target = str;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl Debug for TagStr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq for TagStr {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for TagStr {}
+
+impl PartialOrd for TagStr {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TagStr {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for TagStr {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
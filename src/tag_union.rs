@@ -6,13 +6,13 @@
 
 use crate::{Tag, TagFromStringError};
 #[cfg(feature = "serde")]
-use serde::{de, Deserialize, Deserializer};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::borrow::Borrow;
-use std::collections::HashSet;
+use std::collections::{hash_set, HashSet};
 use std::error::Error;
 use std::fmt::{Display, Formatter};
-use std::hash::{Hash, Hasher};
-use std::iter::FromIterator;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::iter::{Flatten, FromIterator};
 use std::ops::Deref;
 use std::str::FromStr;
 
@@ -36,6 +36,36 @@ use std::str::FromStr;
 pub struct TagUnion(HashSet<Tag>);
 
 impl TagUnion {
+    /// The maximum number of tags in a generated [`Arbitrary`](arbitrary::Arbitrary) union.
+    #[cfg_attr(docsrs, doc(cfg(feature = "arbitrary")))]
+    #[cfg(feature = "arbitrary")]
+    pub const ARBITRARY_MAX_LEN: usize = 8;
+
+    /// Creates an empty union with space for at least `capacity` tags.
+    pub fn with_capacity(capacity: usize) -> TagUnion {
+        Self(HashSet::with_capacity(capacity))
+    }
+
+    /// Creates a union containing just the provided tag.
+    ///
+    /// ```
+    /// use just_a_tag::{tag, TagUnion};
+    ///
+    /// let union = TagUnion::single(tag!("foo"));
+    /// assert_eq!(union, TagUnion::from_str("foo").unwrap());
+    /// assert_eq!(union.is_single().unwrap(), "foo");
+    /// ```
+    pub fn single(tag: Tag) -> TagUnion {
+        let mut set = HashSet::with_capacity(1);
+        set.insert(tag);
+        Self(set)
+    }
+
+    /// Reserves capacity for at least `additional` more tags to be inserted.
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional)
+    }
+
     /// Returns `true` if this tag union matches the value presented in the set.
     ///
     /// ```
@@ -67,6 +97,101 @@ impl TagUnion {
         self.0.is_subset(values)
     }
 
+    /// Returns `true` if this tag union matches the value presented in a set
+    /// using any [`BuildHasher`].
+    ///
+    /// Matching looks up every tag of this union in the provided set, so the set's
+    /// hasher dominates the cost of a match. For non-adversarial input, a faster
+    /// hasher such as `ahash::RandomState` or `fnv::FnvBuildHasher` can be used in
+    /// place of the DoS resistant default [`RandomState`](std::collections::hash_map::RandomState).
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::BuildHasherDefault;
+    /// use just_a_tag::{Tag, TagUnion};
+    ///
+    /// // Hashers from e.g. the `ahash` or `fnv` crates are used the same way.
+    /// type FastSet = HashSet<Tag, BuildHasherDefault<DefaultHasher>>;
+    ///
+    /// let union = TagUnion::from_str("foo+bar").unwrap();
+    /// let tags: FastSet = [Tag::new("foo"), Tag::new("bar")].into_iter().collect();
+    /// assert!(union.matches_set_with_hasher(&tags));
+    /// ```
+    pub fn matches_set_with_hasher<S: BuildHasher>(&self, values: &HashSet<Tag, S>) -> bool {
+        self.0.len() <= values.len() && self.0.iter().all(|tag| values.contains(tag))
+    }
+
+    /// Returns `true` if all tags of this union are contained in the provided tags.
+    ///
+    /// This accepts any collection of tags, such as a [`BTreeSet`](std::collections::BTreeSet)
+    /// or a slice. Prefer [`TagUnion::matches_set`](Self::matches_set) if the tags are
+    /// already held in a [`HashSet`].
+    ///
+    /// ```
+    /// use std::collections::BTreeSet;
+    /// use just_a_tag::{Tag, TagUnion};
+    ///
+    /// let union = TagUnion::from_str("foo+bar").unwrap();
+    /// assert!(union.matches(&BTreeSet::from([Tag::new("foo"), Tag::new("bar")])));
+    /// assert!(!union.matches(&[Tag::new("foo"), Tag::new("baz")]));
+    /// ```
+    pub fn matches<'a, I>(&self, tags: I) -> bool
+    where
+        I: IntoIterator<Item = &'a Tag>,
+    {
+        let tags: HashSet<&Tag> = tags.into_iter().collect();
+        self.0.iter().all(|tag| tags.contains(tag))
+    }
+
+    /// Returns `true` if this tag union contains all tags of the required set.
+    ///
+    /// This is the inverse direction of [`TagUnion::matches_set`](Self::matches_set).
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use just_a_tag::{Tag, TagUnion};
+    ///
+    /// let union = TagUnion::from_str("foo+bar+baz").unwrap();
+    /// assert!(union.is_superset_of(&HashSet::from_iter([Tag::new("foo"), Tag::new("bar")])));
+    /// assert!(!union.is_superset_of(&HashSet::from_iter([Tag::new("foo"), Tag::new("bang")])));
+    /// ```
+    pub fn is_superset_of(&self, required: &HashSet<Tag>) -> bool {
+        self.0.is_superset(required)
+    }
+
+    /// Returns `true` if every set matching this union also matches the other union,
+    /// i.e. if this union contains all tags of the other union.
+    ///
+    /// A rule that implies another rule of the same policy is redundant, as it cannot
+    /// match any set the other rule doesn't.
+    ///
+    /// ```
+    /// use just_a_tag::TagUnion;
+    ///
+    /// let specific = TagUnion::from_str("foo+bar").unwrap();
+    /// let general = TagUnion::from_str("foo").unwrap();
+    /// assert!(specific.implies(&general));
+    /// assert!(!general.implies(&specific));
+    /// ```
+    pub fn implies(&self, other: &TagUnion) -> bool {
+        self.0.is_superset(&other.0)
+    }
+
+    /// Returns `true` if this tag union shares no tags with the provided set.
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use just_a_tag::{Tag, TagUnion};
+    ///
+    /// let union = TagUnion::from_str("foo+bar").unwrap();
+    /// assert!(union.is_disjoint(&HashSet::from_iter([Tag::new("baz")])));
+    /// assert!(!union.is_disjoint(&HashSet::from_iter([Tag::new("bar"), Tag::new("baz")])));
+    /// ```
+    pub fn is_disjoint(&self, set: &HashSet<Tag>) -> bool {
+        self.0.is_disjoint(set)
+    }
+
     /// Inserts a tag into this union.
     /// Returns whether the tag was inserted; that is:
     ///
@@ -85,6 +210,66 @@ impl TagUnion {
         self.0.remove(tag.borrow())
     }
 
+    /// Replaces a tag of this union with another, e.g. when renaming a tag.
+    /// Returns whether the tag was replaced; that is:
+    ///
+    /// * If the old tag was present, it is removed, the new tag is inserted and `true` is returned,
+    /// * If the old tag was not present, the union is left unchanged and `false` is returned.
+    ///
+    /// If the new tag is already present, the union only loses the old tag.
+    ///
+    /// ```
+    /// use just_a_tag::{Tag, TagUnion};
+    ///
+    /// let mut union = TagUnion::from_str("foo+bar").unwrap();
+    /// assert!(union.replace(&Tag::new("foo"), Tag::new("baz")));
+    /// assert_eq!(union, TagUnion::from_str("bar+baz").unwrap());
+    ///
+    /// assert!(!union.replace(&Tag::new("foo"), Tag::new("qux")));
+    /// assert_eq!(union, TagUnion::from_str("bar+baz").unwrap());
+    /// ```
+    pub fn replace(&mut self, old: &Tag, new: Tag) -> bool {
+        if !self.0.remove(old) {
+            return false;
+        }
+
+        self.0.insert(new);
+        true
+    }
+
+    /// Removes all tags from this union, keeping the allocated memory.
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    /// Moves all tags of `other` into this union, leaving `other` empty.
+    ///
+    /// ```
+    /// use just_a_tag::TagUnion;
+    ///
+    /// let mut union = TagUnion::from_str("foo+bar").unwrap();
+    /// let mut other = TagUnion::from_str("bar+baz").unwrap();
+    /// union.append(&mut other);
+    /// assert_eq!(union, TagUnion::from_str("foo+bar+baz").unwrap());
+    /// assert!(other.is_empty());
+    /// ```
+    pub fn append(&mut self, other: &mut TagUnion) {
+        self.0.extend(other.0.drain())
+    }
+
+    /// Retains only the tags for which the predicate returns `true`.
+    ///
+    /// ```
+    /// use just_a_tag::TagUnion;
+    ///
+    /// let mut union = TagUnion::from_str("foo+tmp-bar+tmp-baz").unwrap();
+    /// union.retain(|tag| !tag.starts_with("tmp-"));
+    /// assert_eq!(union, TagUnion::from_str("foo").unwrap());
+    /// ```
+    pub fn retain<F: FnMut(&Tag) -> bool>(&mut self, f: F) {
+        self.0.retain(f)
+    }
+
     /// Returns whether this union contains the specified tag. That is:
     ///
     /// * If the tag was previously inserted, `true` is returned,
@@ -93,30 +278,487 @@ impl TagUnion {
         self.0.contains(tag.borrow())
     }
 
+    /// Attempts to parse one [`TagUnion`] per line of the input.
+    ///
+    /// Lines are trimmed and empty lines are skipped. Errors report the
+    /// (one-based) number of the offending line, and the byte offset within that line.
+    ///
+    /// ```
+    /// use just_a_tag::{TagFromStringError, TagUnion, TagUnionFromStringError};
+    ///
+    /// let unions = TagUnion::parse_many("foo+bar\n\n  baz\nqux+quux").unwrap();
+    /// assert_eq!(unions.len(), 3);
+    ///
+    /// assert_eq!(
+    ///     TagUnion::parse_many("foo\nbar-"),
+    ///     Err(TagUnionFromStringError::InvalidTagOnLine(
+    ///         2,
+    ///         TagFromStringError::MustEndAlphanumeric('-', 3)
+    ///     ))
+    /// );
+    /// ```
+    pub fn parse_many<S: AsRef<str>>(input: S) -> Result<Vec<TagUnion>, TagUnionFromStringError> {
+        let mut unions = Vec::new();
+        for (number, line) in input.as_ref().lines().enumerate() {
+            let leading = line.len() - line.trim_start().len();
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            match TagUnion::parse_at(line, '+', leading) {
+                Ok(union) => unions.push(union),
+                Err(e) => return Err(TagUnionFromStringError::InvalidTagOnLine(number + 1, e)),
+            }
+        }
+
+        Ok(unions)
+    }
+
+    /// Attempts to build a [`TagUnion`] from string-like values, parsing each into a [`Tag`].
+    ///
+    /// Empty values are skipped, as with [`TagUnion::from_str`](Self::from_str).
+    /// Stops at the first invalid value.
+    ///
+    /// ```
+    /// use just_a_tag::{Tag, TagUnion};
+    ///
+    /// let union = TagUnion::try_from_iter(["foo", "bar"]).unwrap();
+    /// assert!(union.contains(&Tag::new("foo")));
+    /// assert!(union.contains(&Tag::new("bar")));
+    ///
+    /// assert!(TagUnion::try_from_iter(vec![String::from("foo"), String::from("bar-")]).is_err());
+    /// ```
+    pub fn try_from_iter<I, S>(iter: I) -> Result<TagUnion, TagUnionFromStringError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        collect_non_empty(
+            iter.into_iter()
+                .map(|value| Tag::from_str(value).map_err(TagUnionFromStringError::from)),
+        )
+    }
+
+    /// Parses string-like values into tags and adds them to this union.
+    ///
+    /// Empty values are skipped. Stops at the first invalid value, in which case
+    /// the union is left unchanged.
+    ///
+    /// ```
+    /// use just_a_tag::{Tag, TagUnion};
+    ///
+    /// let mut union = TagUnion::from_str("foo").unwrap();
+    /// union.extend_str(["bar", "baz"]).unwrap();
+    /// assert_eq!(union, TagUnion::from_str("foo+bar+baz").unwrap());
+    ///
+    /// assert!(union.extend_str(["qux", "bar-"]).is_err());
+    /// assert!(!union.contains(&Tag::new("qux")));
+    /// ```
+    pub fn extend_str<I, S>(&mut self, iter: I) -> Result<(), TagUnionFromStringError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let tags = Self::try_from_iter(iter)?;
+        self.0.extend(tags.0);
+        Ok(())
+    }
+
+    /// Returns a [`proptest`] strategy generating unions of between one and eight valid tags.
+    ///
+    /// ```
+    /// use just_a_tag::TagUnion;
+    /// use proptest::prelude::*;
+    ///
+    /// proptest!(|(union in TagUnion::arb())| {
+    ///     prop_assert!(!union.is_empty());
+    /// });
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "proptest")))]
+    #[cfg(feature = "proptest")]
+    pub fn arb() -> impl proptest::strategy::Strategy<Value = TagUnion> {
+        use proptest::strategy::Strategy;
+        proptest::collection::hash_set(Tag::arb(), 1..=8).prop_map(Self)
+    }
+
+    /// Returns an iterator over the tags in this union, in arbitrary order.
+    ///
+    /// ```
+    /// use just_a_tag::TagUnion;
+    ///
+    /// let union = TagUnion::from_str("foo+bar").unwrap();
+    /// let mut tags: Vec<_> = union.iter().map(|tag| tag.as_str()).collect();
+    /// tags.sort();
+    /// assert_eq!(tags, ["bar", "foo"]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &Tag> {
+        self.0.iter()
+    }
+
+    /// Returns an iterator over the tags in this union, in lexicographic order.
+    ///
+    /// ```
+    /// use just_a_tag::TagUnion;
+    ///
+    /// let union = TagUnion::from_str("foo+bar+baz").unwrap();
+    /// let tags: Vec<_> = union.iter_sorted().map(|tag| tag.as_str()).collect();
+    /// assert_eq!(tags, ["bar", "baz", "foo"]);
+    /// ```
+    pub fn iter_sorted(&self) -> impl Iterator<Item = &Tag> {
+        sorted(&self.0)
+    }
+
+    /// Returns the canonical string form of this union: its tags sorted lexicographically
+    /// and joined by `+`. This is the same as the [`Display`] output.
+    ///
+    /// Since unions are sets, parsing ignores the order and repetition of tags;
+    /// unions that are equal always have the same canonical form, which makes it
+    /// suitable as a stable key, e.g. in maps or caches.
+    ///
+    /// ```
+    /// use just_a_tag::TagUnion;
+    ///
+    /// let a = TagUnion::from_str("foo+foo+bar").unwrap();
+    /// let b = TagUnion::from_str("bar+foo").unwrap();
+    /// assert_eq!(a, b);
+    /// assert_eq!(a.canonical(), "bar+foo");
+    /// assert_eq!(a.canonical(), b.canonical());
+    /// ```
+    pub fn canonical(&self) -> String {
+        self.to_string()
+    }
+
+    /// Returns the tag if this union consists of exactly one tag, or `None` otherwise.
+    ///
+    /// This allows special-casing single-tag unions, e.g. with a direct
+    /// [`HashSet::contains`] check instead of [`TagUnion::matches_set`](Self::matches_set).
+    ///
+    /// ```
+    /// use just_a_tag::TagUnion;
+    ///
+    /// assert_eq!(TagUnion::from_str("foo").unwrap().is_single().unwrap(), "foo");
+    /// assert_eq!(TagUnion::from_str("foo+bar").unwrap().is_single(), None);
+    /// assert_eq!(TagUnion::default().is_single(), None);
+    /// ```
+    pub fn is_single(&self) -> Option<&Tag> {
+        if self.0.len() == 1 {
+            self.0.iter().next()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the longest tag in this union, or `None` if the union is empty.
+    ///
+    /// Among tags of equal length, the lexicographically smallest one is returned.
+    ///
+    /// ```
+    /// use just_a_tag::TagUnion;
+    ///
+    /// let union = TagUnion::from_str("foo+bar+quux+baaz").unwrap();
+    /// assert_eq!(union.longest().unwrap(), "baaz");
+    /// assert_eq!(TagUnion::default().longest(), None);
+    /// ```
+    pub fn longest(&self) -> Option<&Tag> {
+        self.0
+            .iter()
+            .min_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)))
+    }
+
+    /// Returns the shortest tag in this union, or `None` if the union is empty.
+    ///
+    /// Among tags of equal length, the lexicographically smallest one is returned.
+    ///
+    /// ```
+    /// use just_a_tag::TagUnion;
+    ///
+    /// let union = TagUnion::from_str("foo+bar+quux").unwrap();
+    /// assert_eq!(union.shortest().unwrap(), "bar");
+    /// assert_eq!(TagUnion::default().shortest(), None);
+    /// ```
+    pub fn shortest(&self) -> Option<&Tag> {
+        self.0
+            .iter()
+            .min_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+    }
+
+    /// Returns a new union containing the tags of both this and the other union.
+    ///
+    /// ```
+    /// use just_a_tag::TagUnion;
+    ///
+    /// let a = TagUnion::from_str("foo+bar").unwrap();
+    /// let b = TagUnion::from_str("bar+baz").unwrap();
+    /// assert_eq!(a.union(&b), TagUnion::from_str("foo+bar+baz").unwrap());
+    /// ```
+    pub fn union(&self, other: &TagUnion) -> TagUnion {
+        Self(self.0.union(&other.0).cloned().collect())
+    }
+
+    /// Returns a new union containing only the tags present in both this and the other union.
+    ///
+    /// ```
+    /// use just_a_tag::TagUnion;
+    ///
+    /// let a = TagUnion::from_str("foo+bar").unwrap();
+    /// let b = TagUnion::from_str("bar+baz").unwrap();
+    /// assert_eq!(a.intersection(&b), TagUnion::from_str("bar").unwrap());
+    /// ```
+    pub fn intersection(&self, other: &TagUnion) -> TagUnion {
+        Self(self.0.intersection(&other.0).cloned().collect())
+    }
+
+    /// Returns a new union containing the tags of this union that are not in the other union.
+    ///
+    /// ```
+    /// use just_a_tag::TagUnion;
+    ///
+    /// let a = TagUnion::from_str("foo+bar").unwrap();
+    /// let b = TagUnion::from_str("bar+baz").unwrap();
+    /// assert_eq!(a.difference(&b), TagUnion::from_str("foo").unwrap());
+    /// ```
+    pub fn difference(&self, other: &TagUnion) -> TagUnion {
+        Self(self.0.difference(&other.0).cloned().collect())
+    }
+
+    /// Returns the number of tags this union shares with the other union.
+    ///
+    /// ```
+    /// use just_a_tag::TagUnion;
+    ///
+    /// let a = TagUnion::from_str("foo+bar+baz").unwrap();
+    /// let b = TagUnion::from_str("bar+baz+qux").unwrap();
+    /// assert_eq!(a.overlap(&b), 2);
+    /// ```
+    pub fn overlap(&self, other: &TagUnion) -> usize {
+        self.0.intersection(&other.0).count()
+    }
+
+    /// Returns the Jaccard similarity of the two unions, i.e. the size of their
+    /// intersection divided by the size of their union.
+    ///
+    /// The result ranges from `0.0` for disjoint unions to `1.0` for equal unions.
+    /// Two empty unions are considered equal.
+    ///
+    /// ```
+    /// use just_a_tag::TagUnion;
+    ///
+    /// let a = TagUnion::from_str("foo+bar+baz").unwrap();
+    /// let b = TagUnion::from_str("bar+baz+qux").unwrap();
+    /// assert_eq!(a.jaccard(&b), 0.5);
+    /// ```
+    pub fn jaccard(&self, other: &TagUnion) -> f64 {
+        let overlap = self.overlap(other);
+        let union = self.0.len() + other.0.len() - overlap;
+        if union == 0 {
+            return 1.0;
+        }
+
+        overlap as f64 / union as f64
+    }
+
     /// Attempts to parse a [`TagUnion`] from a string-like input.
+    ///
+    /// Error positions are byte offsets into the entire input.
+    ///
+    /// ```
+    /// use just_a_tag::{TagFromStringError, TagUnion, TagUnionFromStringError};
+    ///
+    /// assert_eq!(
+    ///     TagUnion::from_str("foo+#baz"),
+    ///     Err(TagUnionFromStringError::InvalidTag(
+    ///         TagFromStringError::MustStartAlphabetic('#', 4)
+    ///     ))
+    /// );
+    /// ```
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str<S: AsRef<str>>(value: S) -> Result<TagUnion, TagUnionFromStringError> {
-        let value = value.as_ref();
-        if value.is_empty() {
-            return Ok(TagUnion::default());
+        Self::from_str_with_separator(value, '+')
+    }
+
+    /// Attempts to parse a [`TagUnion`] from a string-like input, ignoring ASCII
+    /// whitespace around each `+`-separated part.
+    ///
+    /// ```
+    /// use just_a_tag::{Tag, TagUnion};
+    ///
+    /// let union = TagUnion::from_str_trimmed(" foo + bar ").unwrap();
+    /// assert_eq!(union, TagUnion::from_str("foo+bar").unwrap());
+    /// assert!(TagUnion::from_str(" foo + bar ").is_err());
+    /// ```
+    pub fn from_str_trimmed<S: AsRef<str>>(value: S) -> Result<TagUnion, TagUnionFromStringError> {
+        let mut union = TagUnion::default();
+        let mut start = 0;
+        for part in value.as_ref().split('+') {
+            let trimmed = part.trim_start_matches(|c: char| c.is_ascii_whitespace());
+            let leading = part.len() - trimmed.len();
+            let trimmed = trimmed.trim_end_matches(|c: char| c.is_ascii_whitespace());
+            if !trimmed.is_empty() {
+                let tag = Tag::from_str(trimmed).map_err(|e| e.offset_by(start + leading))?;
+                union.insert(tag);
+            }
+            start += part.len() + 1;
         }
 
-        let parts = value.split('+');
-        let names: HashSet<String> = parts
-            .filter(|&c| !c.contains('+'))
-            .filter(|&c| !c.is_empty())
-            .map(|c| c.into())
-            .collect();
+        Ok(union)
+    }
 
-        if names.is_empty() {
-            return Ok(TagUnion::default());
+    /// Attempts to parse a [`TagUnion`] from a string-like input, using a custom separator
+    /// instead of `+`.
+    ///
+    /// ```
+    /// use just_a_tag::{Tag, TagUnion};
+    ///
+    /// let union = TagUnion::from_str_with_separator("foo|bar", '|').unwrap();
+    /// assert!(union.contains(&Tag::new("foo")));
+    /// assert!(union.contains(&Tag::new("bar")));
+    /// ```
+    pub fn from_str_with_separator<S: AsRef<str>>(
+        value: S,
+        sep: char,
+    ) -> Result<TagUnion, TagUnionFromStringError> {
+        Ok(Self::parse_at(value.as_ref(), sep, 0)?)
+    }
+
+    /// Parses the `sep`-separated parts of a union located at `offset` within a larger
+    /// input, skipping empty parts. Error positions are relative to that input.
+    pub(crate) fn parse_at(
+        value: &str,
+        sep: char,
+        offset: usize,
+    ) -> Result<TagUnion, TagFromStringError> {
+        let mut union = TagUnion::default();
+        let mut start = offset;
+        for part in value.split(sep) {
+            if !part.is_empty() {
+                union.insert(Tag::from_str(part).map_err(|e| e.offset_by(start))?);
+            }
+            start += part.len() + sep.len_utf8();
         }
 
-        let mut tags = HashSet::new();
-        for name in names.into_iter() {
-            tags.insert(Tag::from_str(&name)?);
+        Ok(union)
+    }
+}
+
+/// Assembles a [`TagUnion`] from individual parts, validating each as it is pushed.
+///
+/// Unlike parsing a joined string, errors can be attributed to the specific part that failed.
+/// Like [`TagUnion::from_str`], empty parts are skipped.
+///
+/// ```
+/// use just_a_tag::{Tag, TagFromStringError, TagUnion, TagUnionBuilder};
+///
+/// let mut builder = TagUnionBuilder::new();
+/// builder.push("foo")?.push("bar")?;
+/// assert_eq!(
+///     builder.push("baz-").err(),
+///     Some(TagFromStringError::MustEndAlphanumeric('-', 3))
+/// );
+///
+/// let union = builder.build();
+/// assert_eq!(union, TagUnion::from_str("foo+bar").unwrap());
+/// # Ok::<(), TagFromStringError>(())
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct TagUnionBuilder(HashSet<Tag>);
+
+impl TagUnionBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validates the part and adds it to the union.
+    ///
+    /// On error, the builder is left unchanged.
+    pub fn push(&mut self, s: &str) -> Result<&mut Self, TagFromStringError> {
+        let tag = Tag::from_str(s)?;
+        if !tag.is_empty() {
+            self.0.insert(tag);
         }
 
-        Ok(Self(tags))
+        Ok(self)
+    }
+
+    /// Consumes the builder and returns the assembled union.
+    pub fn build(self) -> TagUnion {
+        TagUnion(self.0)
+    }
+}
+
+/// Parses a [`TagUnion`] incrementally, one character at a time.
+///
+/// This allows embedding union parsing in a larger character-based lexer without
+/// buffering the whole input. Parts are validated as soon as their `+` separator
+/// is pushed; the first error is reported by [`finish`](Self::finish). Like
+/// [`TagUnion::from_str`], empty parts are skipped.
+///
+/// ```
+/// use just_a_tag::{TagUnion, TagUnionParser};
+///
+/// let mut parser = TagUnionParser::new();
+/// for c in "foo+bar".chars() {
+///     parser.push_char(c);
+/// }
+/// assert_eq!(parser.finish().unwrap(), TagUnion::from_str("foo+bar").unwrap());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct TagUnionParser {
+    /// The tags parsed so far.
+    tags: HashSet<Tag>,
+    /// The characters of the current part, reused across parts.
+    part: String,
+    /// The number of bytes pushed so far.
+    offset: usize,
+    /// The first error encountered.
+    error: Option<TagFromStringError>,
+}
+
+impl TagUnionParser {
+    /// Creates a parser for an empty union.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds the next character of the input.
+    ///
+    /// After an invalid part, further characters are ignored.
+    pub fn push_char(&mut self, c: char) {
+        if self.error.is_some() {
+            return;
+        }
+
+        if c == '+' {
+            self.complete_part();
+        } else {
+            self.part.push(c);
+        }
+        self.offset += c.len_utf8();
+    }
+
+    /// Validates the current part, if any, and adds it to the union.
+    fn complete_part(&mut self) {
+        if self.part.is_empty() {
+            return;
+        }
+
+        match Tag::from_str(&self.part) {
+            Ok(tag) => {
+                self.tags.insert(tag);
+            }
+            Err(e) => self.error = Some(e.offset_by(self.offset - self.part.len())),
+        }
+        self.part.clear();
+    }
+
+    /// Completes parsing and returns the union, or the first error encountered.
+    pub fn finish(mut self) -> Result<TagUnion, TagUnionFromStringError> {
+        self.complete_part();
+        match self.error {
+            Some(e) => Err(TagUnionFromStringError::InvalidTag(e)),
+            None => Ok(TagUnion(self.tags)),
+        }
     }
 }
 
@@ -150,30 +792,333 @@ pub trait MatchesAnyTagUnion {
     /// assert!(!unions.matches_set(&set_4));
     /// ```
     fn matches_set(&self, values: &HashSet<Tag>) -> bool;
-}
-
-impl MatchesAnyTagUnion for Vec<TagUnion> {
-    fn matches_set(&self, values: &HashSet<Tag>) -> bool {
-        self.iter().any(|s| s.matches_set(&values))
-    }
-}
 
-impl Deref for TagUnion {
-    type Target = HashSet<Tag>;
-
-    fn deref(&self) -> &Self::Target {
+    /// Returns `true` if none of the tag unions matches the value presented in the set.
+    ///
+    /// This is the complement of [`matches_set`](Self::matches_set), useful for deny lists.
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use just_a_tag::{MatchesAnyTagUnion, Tag, TagUnion};
+    ///
+    /// let denied = [
+    ///     TagUnion::from_str("env-prod+debug").unwrap(),
+    ///     TagUnion::from_str("deprecated").unwrap()
+    /// ];
+    ///
+    /// let allowed = HashSet::from_iter([Tag::new("env-prod")]);
+    /// assert!(denied.matches_none(&allowed));
+    ///
+    /// let forbidden = HashSet::from_iter([Tag::new("env-prod"), Tag::new("debug")]);
+    /// assert!(!denied.matches_none(&forbidden));
+    /// ```
+    fn matches_none(&self, values: &HashSet<Tag>) -> bool {
+        !self.matches_set(values)
+    }
+}
+
+impl MatchesAnyTagUnion for Vec<TagUnion> {
+    fn matches_set(&self, values: &HashSet<Tag>) -> bool {
+        self.as_slice().matches_set(values)
+    }
+}
+
+impl MatchesAnyTagUnion for [TagUnion] {
+    fn matches_set(&self, values: &HashSet<Tag>) -> bool {
+        self.iter().any(|s| s.matches_set(values))
+    }
+}
+
+impl<const N: usize> MatchesAnyTagUnion for [TagUnion; N] {
+    fn matches_set(&self, values: &HashSet<Tag>) -> bool {
+        self.as_slice().matches_set(values)
+    }
+}
+
+impl MatchesAnyTagUnion for HashSet<TagUnion> {
+    fn matches_set(&self, values: &HashSet<Tag>) -> bool {
+        any_matches(self, values)
+    }
+}
+
+/// Extends collections of tag unions, such as `Vec<TagUnion>` or `HashSet<TagUnion>`,
+/// with methods finding the unions that match a set.
+pub trait MatchingUnions {
+    /// Returns the number of tag unions matching the value presented in the set.
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use just_a_tag::{MatchingUnions, Tag, TagUnion};
+    ///
+    /// let rules = vec![
+    ///     TagUnion::from_str("foo").unwrap(),
+    ///     TagUnion::from_str("bar+baz").unwrap(),
+    ///     TagUnion::from_str("qux").unwrap()
+    /// ];
+    ///
+    /// let set = HashSet::from_iter([Tag::new("foo"), Tag::new("bar"), Tag::new("baz")]);
+    /// assert_eq!(rules.count_matches(&set), 2);
+    /// ```
+    fn count_matches(&self, values: &HashSet<Tag>) -> usize;
+
+    /// Returns the tag unions matching the value presented in the set.
+    ///
+    /// For ordered collections, the unions are returned in their original order.
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use just_a_tag::{MatchingUnions, Tag, TagUnion};
+    ///
+    /// let rules = vec![
+    ///     TagUnion::from_str("foo").unwrap(),
+    ///     TagUnion::from_str("qux").unwrap(),
+    ///     TagUnion::from_str("bar+baz").unwrap()
+    /// ];
+    ///
+    /// let set = HashSet::from_iter([Tag::new("foo"), Tag::new("bar"), Tag::new("baz")]);
+    /// assert_eq!(rules.matching(&set), [&rules[0], &rules[2]]);
+    /// ```
+    fn matching<'a>(&'a self, values: &HashSet<Tag>) -> Vec<&'a TagUnion>;
+}
+
+impl<T> MatchingUnions for T
+where
+    T: ?Sized,
+    for<'a> &'a T: IntoIterator<Item = &'a TagUnion>,
+{
+    fn count_matches(&self, values: &HashSet<Tag>) -> usize {
+        self.into_iter().filter(|s| s.matches_set(values)).count()
+    }
+
+    fn matching<'a>(&'a self, values: &HashSet<Tag>) -> Vec<&'a TagUnion> {
+        self.into_iter().filter(|s| s.matches_set(values)).collect()
+    }
+}
+
+/// Returns `true` if any of the provided tag unions matches the value presented in the set.
+///
+/// ```
+/// use std::collections::HashSet;
+/// use just_a_tag::{any_matches, Tag, TagUnion};
+///
+/// let unions = [
+///     TagUnion::from_str("foo").unwrap(),
+///     TagUnion::from_str("bar+baz").unwrap()
+/// ];
+///
+/// let set = HashSet::from_iter([Tag::new("fubar"), Tag::new("bar"), Tag::new("baz")]);
+/// assert!(any_matches(unions.iter().filter(|u| u.len() > 1), &set));
+/// ```
+pub fn any_matches<'a, I: IntoIterator<Item = &'a TagUnion>>(
+    unions: I,
+    set: &HashSet<Tag>,
+) -> bool {
+    unions.into_iter().any(|s| s.matches_set(set))
+}
+
+/// Returns the set of distinct tags used across all provided tag unions.
+///
+/// ```
+/// use just_a_tag::{distinct_tags, Tag, TagUnion};
+///
+/// let unions = [
+///     TagUnion::from_str("foo+bar").unwrap(),
+///     TagUnion::from_str("bar+baz").unwrap()
+/// ];
+///
+/// let tags = distinct_tags(&unions);
+/// assert_eq!(tags.len(), 3);
+/// assert!(tags.contains("baz"));
+/// ```
+pub fn distinct_tags(unions: &[TagUnion]) -> HashSet<Tag> {
+    unions
+        .iter()
+        .flat_map(|union| union.iter().cloned())
+        .collect()
+}
+
+/// Flattens a list of tag unions into the tags of all its unions.
+///
+/// Tags are yielded once per union they appear in; duplicates across unions
+/// are preserved since they are distinct memberships. Use [`distinct_tags`]
+/// to obtain each tag only once.
+///
+/// ```
+/// use just_a_tag::{FlattenTags, Tag, TagUnion};
+///
+/// let policy = vec![
+///     TagUnion::from_str("foo+bar").unwrap(),
+///     TagUnion::from_str("bar+baz").unwrap()
+/// ];
+///
+/// let mut borrowed: Vec<&Tag> = policy.as_slice().flatten_tags().collect();
+/// borrowed.sort();
+/// assert_eq!(borrowed, ["bar", "bar", "baz", "foo"]);
+///
+/// let owned: Vec<Tag> = policy.flatten_tags().collect();
+/// assert_eq!(owned.len(), 4);
+/// ```
+pub trait FlattenTags {
+    /// The iterator over the tags.
+    type Iter: Iterator;
+
+    /// Returns an iterator over the tags of all unions, in arbitrary order.
+    fn flatten_tags(self) -> Self::Iter;
+}
+
+impl FlattenTags for Vec<TagUnion> {
+    type Iter = Flatten<std::vec::IntoIter<TagUnion>>;
+
+    fn flatten_tags(self) -> Self::Iter {
+        self.into_iter().flatten()
+    }
+}
+
+impl<'a> FlattenTags for &'a Vec<TagUnion> {
+    type Iter = Flatten<std::slice::Iter<'a, TagUnion>>;
+
+    fn flatten_tags(self) -> Self::Iter {
+        self.iter().flatten()
+    }
+}
+
+impl<'a> FlattenTags for &'a [TagUnion] {
+    type Iter = Flatten<std::slice::Iter<'a, TagUnion>>;
+
+    fn flatten_tags(self) -> Self::Iter {
+        self.iter().flatten()
+    }
+}
+
+/// Extends iterators over tagged items with filtering by tag unions.
+pub trait FilterByUnions<'a, T>: Iterator<Item = (T, &'a HashSet<Tag>)> + Sized {
+    /// Retains only the items whose tag set matches any of the provided unions.
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use just_a_tag::{FilterByUnions, Tag, TagUnion};
+    ///
+    /// let policy = [
+    ///     TagUnion::from_str("foo").unwrap(),
+    ///     TagUnion::from_str("bar+baz").unwrap()
+    /// ];
+    ///
+    /// let first = HashSet::from_iter([Tag::new("foo")]);
+    /// let second = HashSet::from_iter([Tag::new("bar")]);
+    /// let third = HashSet::from_iter([Tag::new("bar"), Tag::new("baz")]);
+    /// let records = [(1, &first), (2, &second), (3, &third)];
+    ///
+    /// let ids: Vec<_> = records
+    ///     .into_iter()
+    ///     .filter_matching(&policy)
+    ///     .map(|(id, _)| id)
+    ///     .collect();
+    /// assert_eq!(ids, [1, 3]);
+    /// ```
+    fn filter_matching(self, unions: &[TagUnion]) -> FilterMatching<'_, Self> {
+        FilterMatching { iter: self, unions }
+    }
+}
+
+impl<'a, T, I> FilterByUnions<'a, T> for I where I: Iterator<Item = (T, &'a HashSet<Tag>)> {}
+
+/// An iterator retaining the items whose tag set matches any of a list of unions.
+///
+/// This is created by [`FilterByUnions::filter_matching`].
+#[derive(Debug, Clone)]
+pub struct FilterMatching<'u, I> {
+    iter: I,
+    unions: &'u [TagUnion],
+}
+
+impl<'a, 'u, T, I> Iterator for FilterMatching<'u, I>
+where
+    I: Iterator<Item = (T, &'a HashSet<Tag>)>,
+{
+    type Item = (T, &'a HashSet<Tag>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let unions = self.unions;
+        self.iter.find(|(_, tags)| unions.matches_set(tags))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.iter.size_hint();
+        (0, upper)
+    }
+}
+
+impl Deref for TagUnion {
+    type Target = HashSet<Tag>;
+
+    fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
 impl Hash for TagUnion {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        let mut vec = Vec::from_iter(self.0.iter());
-        vec.sort();
-        for tag in vec {
-            tag.hash(state);
+        hash_sorted(&self.0, state)
+    }
+}
+
+impl IntoIterator for TagUnion {
+    type Item = Tag;
+    type IntoIter = hash_set::IntoIter<Tag>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a TagUnion {
+    type Item = &'a Tag;
+    type IntoIter = hash_set::Iter<'a, Tag>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// Returns an iterator over the tags of a set, in lexicographic order.
+pub(crate) fn sorted(tags: &HashSet<Tag>) -> impl Iterator<Item = &Tag> {
+    let mut vec = Vec::from_iter(tags.iter());
+    vec.sort();
+    vec.into_iter()
+}
+
+/// Hashes the tags of a set in lexicographic order, so that equal sets hash equally
+/// regardless of their iteration order.
+pub(crate) fn hash_sorted<H: Hasher>(tags: &HashSet<Tag>, state: &mut H) {
+    for tag in sorted(tags) {
+        tag.hash(state);
+    }
+}
+
+/// Writes the tags of a set in lexicographic order, joined by the separator.
+pub(crate) fn fmt_sorted(
+    tags: &HashSet<Tag>,
+    sep: &str,
+    f: &mut Formatter<'_>,
+) -> std::fmt::Result {
+    for (i, tag) in sorted(tags).enumerate() {
+        if i > 0 {
+            f.write_str(sep)?;
         }
+        f.write_str(tag)?;
     }
+    Ok(())
+}
+
+/// Collects parsed tags into a [`TagUnion`], skipping empty tags as
+/// [`TagUnion::from_str`] does. Stops at the first error.
+fn collect_non_empty<I, E>(tags: I) -> Result<TagUnion, E>
+where
+    I: IntoIterator<Item = Result<Tag, E>>,
+{
+    tags.into_iter()
+        .filter(|tag| !matches!(tag, Ok(tag) if tag.is_empty()))
+        .collect()
 }
 
 impl FromIterator<Tag> for TagUnion {
@@ -182,6 +1127,49 @@ impl FromIterator<Tag> for TagUnion {
     }
 }
 
+impl From<Tag> for TagUnion {
+    fn from(tag: Tag) -> Self {
+        Self::single(tag)
+    }
+}
+
+impl TryFrom<Vec<String>> for TagUnion {
+    type Error = TagUnionFromStringError;
+
+    /// Parses every value into a [`Tag`], reusing the strings' allocations.
+    /// Empty values are skipped. Stops at the first invalid value.
+    fn try_from(values: Vec<String>) -> Result<Self, Self::Error> {
+        collect_non_empty(
+            values
+                .into_iter()
+                .map(|value| Tag::try_from(value).map_err(TagUnionFromStringError::from)),
+        )
+    }
+}
+
+impl TryFrom<&[&str]> for TagUnion {
+    type Error = TagUnionFromStringError;
+
+    /// Parses every value into a [`Tag`]. Empty values are skipped.
+    /// Stops at the first invalid value.
+    fn try_from(values: &[&str]) -> Result<Self, Self::Error> {
+        Self::try_from_iter(values)
+    }
+}
+
+impl Extend<Tag> for TagUnion {
+    fn extend<T: IntoIterator<Item = Tag>>(&mut self, iter: T) {
+        self.0.extend(iter)
+    }
+}
+
+impl Display for TagUnion {
+    /// Formats the union as its canonical `a+b+c` form, with tags sorted lexicographically.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_sorted(&self.0, "+", f)
+    }
+}
+
 impl FromStr for TagUnion {
     type Err = TagUnionFromStringError;
 
@@ -205,15 +1193,162 @@ impl<'de> Deserialize<'de> for TagUnion {
     }
 }
 
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg(feature = "serde")]
+impl Serialize for TagUnion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "arbitrary")))]
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for TagUnion {
+    /// Generates a union of between one and [`TagUnion::ARBITRARY_MAX_LEN`] valid tags.
+    ///
+    /// Duplicate tags are merged, so the union may end up smaller than requested.
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let len = u.int_in_range(1..=Self::ARBITRARY_MAX_LEN)?;
+        let mut tags = HashSet::with_capacity(len);
+        for _ in 0..len {
+            tags.insert(Tag::arbitrary(u)?);
+        }
+        Ok(Self(tags))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Tag::size_hint(depth)
+    }
+}
+
+/// Archives the union as a vector of tags sorted lexicographically,
+/// keeping the archived representation deterministic.
+#[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+#[cfg(feature = "rkyv")]
+impl rkyv::Archive for TagUnion {
+    type Archived = rkyv::vec::ArchivedVec<rkyv::Archived<Tag>>;
+    type Resolver = rkyv::vec::VecResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: rkyv::Place<Self::Archived>) {
+        rkyv::vec::ArchivedVec::resolve_from_len(self.len(), resolver, out);
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+#[cfg(feature = "rkyv")]
+impl<S> rkyv::Serialize<S> for TagUnion
+where
+    S: rkyv::rancor::Fallible + rkyv::ser::Allocator + rkyv::ser::Writer + ?Sized,
+    Tag: rkyv::Serialize<S>,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let tags: Vec<&Tag> = self.iter_sorted().collect();
+        rkyv::vec::ArchivedVec::serialize_from_iter::<Tag, _, _>(tags.iter().copied(), serializer)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+#[cfg(feature = "rkyv")]
+impl<D> rkyv::Deserialize<TagUnion, D> for rkyv::vec::ArchivedVec<rkyv::Archived<Tag>>
+where
+    D: rkyv::rancor::Fallible + ?Sized,
+    D::Error: rkyv::rancor::Source,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<TagUnion, D::Error> {
+        self.iter()
+            .map(|tag| rkyv::Deserialize::<Tag, D>::deserialize(tag, deserializer))
+            .collect()
+    }
+}
+
+/// Serializes a [`TagUnion`] as a sequence of tags rather than the `+`-joined string.
+///
+/// This avoids formatting and parsing the joined string, which is useful for compact
+/// binary formats where both ends are under control. Tags are emitted in lexicographic
+/// order. Use it via `#[serde(with = "just_a_tag::tag_union::as_sequence")]`:
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use just_a_tag::TagUnion;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Rule {
+///     #[serde(with = "just_a_tag::tag_union::as_sequence")]
+///     tags: TagUnion,
+/// }
+///
+/// let rule = Rule { tags: TagUnion::from_str("foo+bar").unwrap() };
+/// let json = serde_json::to_string(&rule).unwrap();
+/// assert_eq!(json, r#"{"tags":["bar","foo"]}"#);
+///
+/// let rule: Rule = serde_json::from_str(&json).unwrap();
+/// assert_eq!(rule.tags, TagUnion::from_str("foo+bar").unwrap());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg(feature = "serde")]
+pub mod as_sequence {
+    use super::TagUnion;
+    use crate::Tag;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Serializes the union as a sequence of tags.
+    pub fn serialize<S>(value: &TagUnion, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(value.iter_sorted())
+    }
+
+    /// Deserializes the union from a sequence of tags, validating each.
+    /// Empty tags are skipped.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<TagUnion, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let tags = Vec::<Tag>::deserialize(deserializer)?;
+        super::collect_non_empty(tags.into_iter().map(Ok))
+    }
+}
+
+/// (De)serializes a [`TagUnion`] as an array of tags, e.g. `["bar","foo"]` in JSON.
+///
+/// This is an alias of [`as_sequence`](mod@as_sequence) for interoperability with
+/// consumers expecting arrays. Use it via `#[serde(with = "just_a_tag::tag_union::as_array")]`:
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use just_a_tag::TagUnion;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Item {
+///     #[serde(with = "just_a_tag::tag_union::as_array")]
+///     tags: TagUnion,
+/// }
+///
+/// let item: Item = serde_json::from_str(r#"{"tags":["foo","bar"]}"#).unwrap();
+/// assert_eq!(serde_json::to_string(&item).unwrap(), r#"{"tags":["bar","foo"]}"#);
+/// assert!(serde_json::from_str::<Item>(r#"{"tags":["foo-"]}"#).is_err());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg(feature = "serde")]
+pub use as_sequence as as_array;
+
 #[derive(Debug, Eq, PartialEq)]
 pub enum TagUnionFromStringError {
     InvalidTag(TagFromStringError),
+    InvalidTagOnLine(usize, TagFromStringError),
 }
 
 impl Display for TagUnionFromStringError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             TagUnionFromStringError::InvalidTag(e) => write!(f, "Invalid tag: {e}"),
+            TagUnionFromStringError::InvalidTagOnLine(line, e) => {
+                write!(f, "Invalid tag on line {line}: {e}")
+            }
         }
     }
 }
@@ -224,7 +1359,14 @@ impl From<TagFromStringError> for TagUnionFromStringError {
     }
 }
 
-impl Error for TagUnionFromStringError {}
+impl Error for TagUnionFromStringError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TagUnionFromStringError::InvalidTag(e) => Some(e),
+            TagUnionFromStringError::InvalidTagOnLine(_, e) => Some(e),
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -237,6 +1379,17 @@ mod tests {
     }
 
     #[test]
+    fn test_capacity() {
+        let mut tags = TagUnion::with_capacity(4);
+        assert!(tags.is_empty());
+        assert!(tags.capacity() >= 4);
+
+        tags.reserve(16);
+        assert!(tags.capacity() >= 16);
+    }
+
+    #[test]
+    #[allow(clippy::needless_borrows_for_generic_args)] // exercises removal by reference
     fn test_add_remove() {
         let mut tags = TagUnion::from_str(r#"foo"#).unwrap();
         assert!(tags.contains(&Tag::new("foo")));
@@ -255,6 +1408,142 @@ mod tests {
         assert!(tags.is_empty());
     }
 
+    #[test]
+    fn test_replace() {
+        let mut tags = TagUnion::from_str("foo+bar").unwrap();
+        assert!(tags.replace(&Tag::new("foo"), Tag::new("baz")));
+        assert_eq!(tags, TagUnion::from_str("bar+baz").unwrap());
+
+        assert!(tags.replace(&Tag::new("baz"), Tag::new("bar")));
+        assert_eq!(tags, TagUnion::from_str("bar").unwrap());
+
+        assert!(tags.replace(&Tag::new("bar"), Tag::new("bar")));
+        assert_eq!(tags, TagUnion::from_str("bar").unwrap());
+
+        assert!(!tags.replace(&Tag::new("foo"), Tag::new("qux")));
+        assert_eq!(tags, TagUnion::from_str("bar").unwrap());
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut tags = TagUnion::from_str("foo+tmp-bar+tmp-baz+bar-tmp").unwrap();
+        tags.retain(|tag| !tag.has_label_prefix("tmp"));
+        assert_eq!(tags.len(), 2);
+        assert!(tags.contains(&Tag::new("foo")));
+        assert!(tags.contains(&Tag::new("bar-tmp")));
+    }
+
+    #[test]
+    fn test_clear_extend_append() {
+        let mut tags = TagUnion::from_str("foo+bar").unwrap();
+        tags.extend([Tag::new("bar"), Tag::new("baz")]);
+        assert_eq!(tags, TagUnion::from_str("foo+bar+baz").unwrap());
+
+        let mut other = TagUnion::from_str("qux").unwrap();
+        tags.append(&mut other);
+        assert_eq!(tags.len(), 4);
+        assert!(tags.contains(&Tag::new("qux")));
+        assert!(other.is_empty());
+
+        tags.clear();
+        assert!(tags.is_empty());
+    }
+
+    #[test]
+    fn test_set_operations_disjoint() {
+        let a = TagUnion::from_str("foo+bar").unwrap();
+        let b = TagUnion::from_str("baz").unwrap();
+        assert_eq!(a.union(&b), TagUnion::from_str("foo+bar+baz").unwrap());
+        assert!(a.intersection(&b).is_empty());
+        assert_eq!(a.difference(&b), a);
+        assert_eq!(b.difference(&a), b);
+    }
+
+    #[test]
+    fn test_set_operations_overlapping() {
+        let a = TagUnion::from_str("foo+bar").unwrap();
+        let b = TagUnion::from_str("bar+baz").unwrap();
+        assert_eq!(a.union(&b), TagUnion::from_str("foo+bar+baz").unwrap());
+        assert_eq!(a.intersection(&b), TagUnion::from_str("bar").unwrap());
+        assert_eq!(a.difference(&b), TagUnion::from_str("foo").unwrap());
+        assert_eq!(b.difference(&a), TagUnion::from_str("baz").unwrap());
+    }
+
+    #[test]
+    fn test_set_operations_identical() {
+        let a = TagUnion::from_str("foo+bar").unwrap();
+        let b = a.clone();
+        assert_eq!(a.union(&b), a);
+        assert_eq!(a.intersection(&b), a);
+        assert!(a.difference(&b).is_empty());
+    }
+
+    #[test]
+    fn test_overlap_and_jaccard() {
+        let a = TagUnion::from_str("foo+bar").unwrap();
+        let b = TagUnion::from_str("bar+baz+qux").unwrap();
+        let c = TagUnion::from_str("qux").unwrap();
+        let empty = TagUnion::default();
+
+        assert_eq!(a.overlap(&b), 1);
+        assert_eq!(b.overlap(&a), 1);
+        assert_eq!(a.overlap(&c), 0);
+        assert_eq!(a.overlap(&a), 2);
+
+        assert_eq!(a.jaccard(&b), 0.25);
+        assert_eq!(b.jaccard(&a), 0.25);
+        assert_eq!(a.jaccard(&c), 0.0);
+        assert_eq!(a.jaccard(&a), 1.0);
+        assert_eq!(a.jaccard(&empty), 0.0);
+        assert_eq!(empty.jaccard(&empty), 1.0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(TagUnion::default().to_string(), "");
+        assert_eq!(TagUnion::from_str("foo").unwrap().to_string(), "foo");
+        assert_eq!(
+            TagUnion::from_str("foo+bar+baz").unwrap().to_string(),
+            "bar+baz+foo"
+        );
+    }
+
+    #[test]
+    fn test_canonical() {
+        let inputs = ["foo+foo+bar", "bar+foo", "foo+bar+bar+foo", "bar+foo+bar"];
+        let unions: Vec<_> = inputs
+            .into_iter()
+            .map(|input| TagUnion::from_str(input).unwrap())
+            .collect();
+
+        for union in &unions {
+            assert_eq!(union, &unions[0]);
+            assert_eq!(union.canonical(), "bar+foo");
+        }
+
+        assert_eq!(TagUnion::default().canonical(), "");
+        assert_ne!(
+            TagUnion::from_str("foo").unwrap().canonical(),
+            TagUnion::from_str("foo+bar").unwrap().canonical()
+        );
+    }
+
+    #[test]
+    fn test_display_deterministic() {
+        let mut a = TagUnion::default();
+        a.insert(Tag::new("foo"));
+        a.insert(Tag::new("bar"));
+        a.insert(Tag::new("baz"));
+
+        let mut b = TagUnion::default();
+        b.insert(Tag::new("baz"));
+        b.insert(Tag::new("foo"));
+        b.insert(Tag::new("bar"));
+
+        assert_eq!(a.to_string(), b.to_string());
+        assert_eq!(TagUnion::from_str(a.to_string()).unwrap(), a);
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn test_trivial() {
@@ -272,17 +1561,43 @@ mod tests {
         assert!(tags.contains(&Tag::new("baz")));
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_roundtrip() {
+        let tags = TagUnion::from_str("foo+bar+baz").unwrap();
+        let json = serde_json::to_string(&tags).unwrap();
+        assert_eq!(json, r#""bar+baz+foo""#);
+        assert_eq!(serde_json::from_str::<TagUnion>(&json).unwrap(), tags);
+    }
+
     #[test]
     fn test_invalid() {
         let tags = TagUnion::from_str(r#"foo+#baz"#);
         assert_eq!(
             tags,
             Err(TagUnionFromStringError::InvalidTag(
-                crate::TagFromStringError::MustStartAlphabetic('#')
+                crate::TagFromStringError::MustStartAlphabetic('#', 4)
             ))
         );
     }
 
+    #[test]
+    fn test_error_source() {
+        let error =
+            TagUnionFromStringError::InvalidTag(TagFromStringError::InvalidCharacter('_', 3));
+        let source = error.source().unwrap();
+        assert_eq!(
+            source.downcast_ref::<TagFromStringError>(),
+            Some(&TagFromStringError::InvalidCharacter('_', 3))
+        );
+
+        let error = TagUnion::parse_many("foo\nbar-").unwrap_err();
+        assert_eq!(
+            error.source().unwrap().to_string(),
+            TagFromStringError::MustEndAlphanumeric('-', 3).to_string()
+        );
+    }
+
     #[test]
     fn test_matches() {
         let selections = vec![
@@ -309,4 +1624,560 @@ mod tests {
         // neither foo+bar, nor baz are present.
         assert!(!selections.matches_set(&HashSet::from_iter([Tag::new("foo"), Tag::new("bang"),])));
     }
+
+    #[test]
+    fn test_matches_slice_and_array() {
+        let selections = [
+            TagUnion::from_str("foo+bar").unwrap(),
+            TagUnion::from_str("baz").unwrap(),
+        ];
+        let set = HashSet::from_iter([Tag::new("foo"), Tag::new("bar")]);
+        let other = HashSet::from_iter([Tag::new("foo"), Tag::new("bang")]);
+
+        assert!(selections.matches_set(&set));
+        assert!(!selections.matches_set(&other));
+
+        let slice: &[TagUnion] = &selections;
+        assert!(slice.matches_set(&set));
+        assert!(!slice.matches_set(&other));
+    }
+
+    #[test]
+    fn test_matches_hash_set() {
+        let selections = HashSet::from_iter([
+            TagUnion::from_str("foo+bar").unwrap(),
+            TagUnion::from_str("baz").unwrap(),
+        ]);
+
+        assert!(selections.matches_set(&HashSet::from_iter([Tag::new("baz")])));
+        assert!(!selections.matches_set(&HashSet::from_iter([Tag::new("foo")])));
+        assert!(any_matches(
+            &selections,
+            &HashSet::from_iter([Tag::new("foo"), Tag::new("bar")])
+        ));
+    }
+
+    #[test]
+    fn test_filter_matching() {
+        let unions = [
+            TagUnion::from_str("foo").unwrap(),
+            TagUnion::from_str("bar+baz").unwrap(),
+        ];
+
+        let foo = HashSet::from_iter([Tag::new("foo")]);
+        let bar = HashSet::from_iter([Tag::new("bar")]);
+        let bar_baz = HashSet::from_iter([Tag::new("bar"), Tag::new("baz")]);
+        let records = vec![("a", &foo), ("b", &bar), ("c", &bar_baz)];
+
+        let matching: Vec<_> = records.into_iter().filter_matching(&unions).collect();
+        assert_eq!(matching, [("a", &foo), ("c", &bar_baz)]);
+
+        let none: Vec<_> = [("a", &foo)].into_iter().filter_matching(&[]).collect();
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_count_matches() {
+        let unions = vec![
+            TagUnion::from_str("foo").unwrap(),
+            TagUnion::from_str("foo+bar").unwrap(),
+            TagUnion::from_str("baz").unwrap(),
+        ];
+
+        let set = HashSet::from([Tag::new("foo"), Tag::new("bar")]);
+        assert_eq!(unions.count_matches(&set), 2);
+        assert_eq!(unions[..1].count_matches(&set), 1);
+        assert_eq!(unions.count_matches(&HashSet::new()), 0);
+
+        let unions: HashSet<TagUnion> = unions.into_iter().collect();
+        assert_eq!(unions.count_matches(&set), 2);
+    }
+
+    #[test]
+    fn test_custom_matches_any() {
+        // Implementors only need to provide `matches_set`.
+        struct Always;
+        impl MatchesAnyTagUnion for Always {
+            fn matches_set(&self, _values: &HashSet<Tag>) -> bool {
+                true
+            }
+        }
+
+        assert!(Always.matches_set(&HashSet::new()));
+        assert!(!Always.matches_none(&HashSet::new()));
+    }
+
+    #[test]
+    fn test_matching() {
+        let unions = vec![
+            TagUnion::from_str("foo").unwrap(),
+            TagUnion::from_str("baz").unwrap(),
+            TagUnion::from_str("foo+bar").unwrap(),
+        ];
+
+        let set = HashSet::from([Tag::new("foo"), Tag::new("bar")]);
+        assert_eq!(unions.matching(&set), [&unions[0], &unions[2]]);
+        assert!(unions.matching(&HashSet::new()).is_empty());
+
+        let unions: HashSet<TagUnion> = unions.into_iter().collect();
+        let matched = unions.matching(&set);
+        assert_eq!(matched.len(), 2);
+        assert!(matched.contains(&&TagUnion::from_str("foo+bar").unwrap()));
+    }
+
+    #[test]
+    fn test_matches_none() {
+        let unions = vec![
+            TagUnion::from_str("foo+bar").unwrap(),
+            TagUnion::from_str("baz").unwrap(),
+        ];
+
+        assert!(unions.matches_none(&HashSet::from([Tag::new("foo")])));
+        assert!(!unions.matches_none(&HashSet::from([Tag::new("baz")])));
+        assert!(!unions.matches_none(&HashSet::from([Tag::new("foo"), Tag::new("bar")])));
+        assert!(unions[..0].matches_none(&HashSet::from([Tag::new("foo")])));
+    }
+
+    #[test]
+    fn test_matches_set_with_hasher() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::BuildHasherDefault;
+
+        let set: HashSet<Tag, BuildHasherDefault<DefaultHasher>> =
+            [Tag::new("foo"), Tag::new("bar")].into_iter().collect();
+
+        assert!(TagUnion::from_str("foo+bar")
+            .unwrap()
+            .matches_set_with_hasher(&set));
+        assert!(TagUnion::from_str("foo")
+            .unwrap()
+            .matches_set_with_hasher(&set));
+        assert!(TagUnion::default().matches_set_with_hasher(&set));
+        assert!(!TagUnion::from_str("foo+baz")
+            .unwrap()
+            .matches_set_with_hasher(&set));
+        assert!(!TagUnion::from_str("foo+bar+baz")
+            .unwrap()
+            .matches_set_with_hasher(&set));
+        // The union itself keeps the default hasher, so it interoperates with `HashSet<Tag>`.
+        let union = TagUnion::from_str("foo").unwrap();
+        let inner: &HashSet<Tag> = &union;
+        assert!(inner.is_subset(&HashSet::from([Tag::new("foo")])));
+    }
+
+    #[test]
+    fn test_flatten_tags() {
+        let unions = vec![
+            TagUnion::from_str("foo+bar").unwrap(),
+            TagUnion::from_str("foo").unwrap(),
+            TagUnion::default(),
+        ];
+
+        let mut borrowed: Vec<_> = (&unions).flatten_tags().collect();
+        borrowed.sort();
+        assert_eq!(borrowed, ["bar", "foo", "foo"]);
+        assert_eq!(unions[..1].flatten_tags().count(), 2);
+
+        let mut owned: Vec<Tag> = unions.flatten_tags().collect();
+        owned.sort();
+        assert_eq!(owned, [Tag::new("bar"), Tag::new("foo"), Tag::new("foo")]);
+        assert_eq!(Vec::<TagUnion>::new().flatten_tags().count(), 0);
+    }
+
+    #[test]
+    fn test_implies() {
+        let a = TagUnion::from_str("foo+bar").unwrap();
+        let b = TagUnion::from_str("foo").unwrap();
+        let c = TagUnion::from_str("baz").unwrap();
+        let empty = TagUnion::default();
+
+        assert!(a.implies(&a));
+        assert!(a.implies(&b));
+        assert!(!b.implies(&a));
+        assert!(!a.implies(&c));
+        assert!(!c.implies(&a));
+        assert!(a.implies(&empty));
+        assert!(!empty.implies(&a));
+
+        let set = HashSet::from([Tag::new("foo"), Tag::new("bar")]);
+        assert!(a.matches_set(&set) && b.matches_set(&set));
+    }
+
+    #[test]
+    fn test_is_disjoint() {
+        let union = TagUnion::from_str("foo+bar").unwrap();
+        assert!(union.is_disjoint(&HashSet::new()));
+        assert!(union.is_disjoint(&HashSet::from([Tag::new("baz")])));
+        assert!(!union.is_disjoint(&HashSet::from([Tag::new("foo")])));
+        assert!(TagUnion::default().is_disjoint(&HashSet::from([Tag::new("foo")])));
+    }
+
+    #[test]
+    fn test_distinct_tags() {
+        let unions = [
+            TagUnion::from_str("foo+bar").unwrap(),
+            TagUnion::from_str("bar").unwrap(),
+            TagUnion::from_str("baz+foo").unwrap(),
+        ];
+
+        let tags = distinct_tags(&unions);
+        assert_eq!(
+            tags,
+            HashSet::from([Tag::new("foo"), Tag::new("bar"), Tag::new("baz")])
+        );
+        assert!(distinct_tags(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_is_superset_of() {
+        let union = TagUnion::from_str("foo+bar").unwrap();
+        assert!(union.is_superset_of(&HashSet::new()));
+        assert!(union.is_superset_of(&HashSet::from_iter([Tag::new("foo")])));
+        assert!(union.is_superset_of(&HashSet::from_iter([Tag::new("foo"), Tag::new("bar")])));
+        assert!(!union.is_superset_of(&HashSet::from_iter([
+            Tag::new("foo"),
+            Tag::new("bar"),
+            Tag::new("baz")
+        ])));
+    }
+
+    #[test]
+    fn test_iter() {
+        let union = TagUnion::from_str("foo+bar").unwrap();
+
+        let sorted: Vec<&Tag> = union.iter_sorted().collect();
+        assert_eq!(sorted, [&Tag::new("bar"), &Tag::new("foo")]);
+
+        let mut borrowed = Vec::new();
+        for tag in &union {
+            borrowed.push(tag.clone());
+        }
+        borrowed.sort();
+        assert_eq!(borrowed, [Tag::new("bar"), Tag::new("foo")]);
+
+        let mut owned: Vec<Tag> = union.into_iter().collect();
+        owned.sort();
+        assert_eq!(owned, borrowed);
+    }
+
+    #[test]
+    fn test_is_single() {
+        let tags = TagUnion::from_str("foo").unwrap();
+        assert_eq!(tags.is_single(), Some(&Tag::new("foo")));
+        assert_eq!(TagUnion::from_str("foo+bar").unwrap().is_single(), None);
+        assert_eq!(TagUnion::default().is_single(), None);
+    }
+
+    #[test]
+    fn test_longest_shortest() {
+        let tags = TagUnion::from_str("ccc+bb+aaa+dd+e").unwrap();
+        assert_eq!(tags.longest(), Some(&Tag::new("aaa")));
+        assert_eq!(tags.shortest(), Some(&Tag::new("e")));
+
+        let tags = TagUnion::from_str("bb+aa").unwrap();
+        assert_eq!(tags.longest(), Some(&Tag::new("aa")));
+        assert_eq!(tags.shortest(), Some(&Tag::new("aa")));
+
+        let tags = TagUnion::default();
+        assert_eq!(tags.longest(), None);
+        assert_eq!(tags.shortest(), None);
+    }
+
+    #[test]
+    fn test_from_str_trimmed() {
+        let tags = TagUnion::from_str_trimmed("\tfoo +bar+ baz ").unwrap();
+        assert_eq!(tags, TagUnion::from_str("foo+bar+baz").unwrap());
+        assert!(TagUnion::from_str_trimmed("  ").unwrap().is_empty());
+        assert_eq!(TagUnion::from_str_trimmed("foo + +bar").unwrap().len(), 2);
+        assert_eq!(
+            TagUnion::from_str_trimmed("foo + b ar"),
+            Err(TagUnionFromStringError::InvalidTag(
+                TagFromStringError::InvalidCharacter(' ', 7)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_custom_separator() {
+        let tags = TagUnion::from_str_with_separator("foo|bar||", '|').unwrap();
+        assert_eq!(tags.len(), 2);
+        assert!(tags.contains(&Tag::new("foo")));
+        assert!(tags.contains(&Tag::new("bar")));
+
+        assert!(TagUnion::from_str_with_separator("foo+bar", '|').is_err());
+        assert_eq!(
+            TagUnion::from_str_with_separator("foo||b_r", '|'),
+            Err(TagUnionFromStringError::InvalidTag(
+                TagFromStringError::InvalidCharacter('_', 6)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_single() {
+        let union = TagUnion::single(Tag::new("foo"));
+        assert_eq!(union.len(), 1);
+        assert!(union.contains(&Tag::new("foo")));
+        assert_eq!(TagUnion::from(Tag::new("foo")), union);
+        assert!(union.matches_set(&HashSet::from([Tag::new("foo"), Tag::new("bar")])));
+    }
+
+    #[test]
+    fn test_try_from_vec_and_slice() {
+        let values = vec![String::from("foo"), String::from("bar")];
+        let union = TagUnion::try_from(values).unwrap();
+        assert_eq!(union, TagUnion::from_str("foo+bar").unwrap());
+
+        let values: &[&str] = &["foo", "bar", "foo"];
+        let union = TagUnion::try_from(values).unwrap();
+        assert_eq!(union, TagUnion::from_str("foo+bar").unwrap());
+
+        let values = vec![String::new(), String::from("foo")];
+        assert_eq!(
+            TagUnion::try_from(values).unwrap(),
+            TagUnion::from_str("foo").unwrap()
+        );
+        let values: &[&str] = &["", "foo", ""];
+        assert_eq!(
+            TagUnion::try_from(values).unwrap(),
+            TagUnion::from_str("foo").unwrap()
+        );
+
+        let values = vec![String::from("foo"), String::from("bar-")];
+        assert_eq!(
+            TagUnion::try_from(values),
+            Err(TagUnionFromStringError::InvalidTag(
+                TagFromStringError::MustEndAlphanumeric('-', 3)
+            ))
+        );
+
+        let values: &[&str] = &["_foo"];
+        assert_eq!(
+            TagUnion::try_from(values),
+            Err(TagUnionFromStringError::InvalidTag(
+                TagFromStringError::MustStartAlphabetic('_', 0)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_builder() {
+        let mut builder = TagUnionBuilder::new();
+        builder
+            .push("foo")
+            .unwrap()
+            .push("")
+            .unwrap()
+            .push("foo")
+            .unwrap();
+        assert_eq!(
+            builder.push("bar_baz").err(),
+            Some(TagFromStringError::InvalidCharacter('_', 3))
+        );
+        builder.push("bar").unwrap();
+
+        let union = builder.build();
+        assert_eq!(union.len(), 2);
+        assert!(union.contains(&Tag::new("foo")));
+        assert!(union.contains(&Tag::new("bar")));
+
+        assert!(TagUnionBuilder::new().build().is_empty());
+    }
+
+    #[test]
+    fn test_extend_str() {
+        let mut union = TagUnion::default();
+        union.extend_str(["foo", "bar"]).unwrap();
+        union
+            .extend_str(vec![String::from("bar"), String::from("baz")])
+            .unwrap();
+        assert_eq!(union, TagUnion::from_str("foo+bar+baz").unwrap());
+
+        union.extend_str(["", "qux"]).unwrap();
+        assert!(!union.contains(&Tag::EMPTY));
+        assert_eq!(union.to_string(), "bar+baz+foo+qux");
+        union.remove(Tag::new("qux"));
+
+        assert_eq!(
+            union.extend_str(["qux", "a_b", "quux"]),
+            Err(TagUnionFromStringError::InvalidTag(
+                TagFromStringError::InvalidCharacter('_', 1)
+            ))
+        );
+        assert_eq!(union, TagUnion::from_str("foo+bar+baz").unwrap());
+    }
+
+    #[test]
+    fn test_parser() {
+        let parse = |input: &str| {
+            let mut parser = TagUnionParser::new();
+            input.chars().for_each(|c| parser.push_char(c));
+            parser.finish()
+        };
+
+        for input in [
+            "",
+            "+",
+            "foo",
+            "foo+bar",
+            "+foo++bar+",
+            "foo+foo",
+            "foo-",
+            "a+b_c+d",
+        ] {
+            assert_eq!(parse(input), TagUnion::from_str(input), "{input}");
+        }
+
+        assert_eq!(
+            parse("foo-+bar_"),
+            Err(TagUnionFromStringError::InvalidTag(
+                TagFromStringError::MustEndAlphanumeric('-', 3)
+            ))
+        );
+        assert_eq!(
+            parse("foo+bar_"),
+            Err(TagUnionFromStringError::InvalidTag(
+                TagFromStringError::InvalidCharacter('_', 7)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_try_from_iter() {
+        let tags = TagUnion::try_from_iter(["foo", "bar", "foo"]).unwrap();
+        assert_eq!(tags, TagUnion::from_str("foo+bar").unwrap());
+
+        let tags = TagUnion::try_from_iter(["", "foo", ""]).unwrap();
+        assert_eq!(tags, TagUnion::from_str("foo").unwrap());
+        assert!(!tags.contains(&Tag::EMPTY));
+        assert_eq!(TagUnion::from_str(tags.to_string()).unwrap(), tags);
+        assert!(TagUnion::try_from_iter([""]).unwrap().is_empty());
+
+        assert_eq!(
+            TagUnion::try_from_iter(["foo", "a_b", "-"]),
+            Err(TagUnionFromStringError::InvalidTag(
+                TagFromStringError::InvalidCharacter('_', 1)
+            ))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn test_arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let data: Vec<u8> = (0..=255).rev().cycle().take(8192).collect();
+        let mut u = Unstructured::new(&data);
+        for _ in 0..8 {
+            let tags = TagUnion::arbitrary(&mut u).unwrap();
+            assert!(!tags.is_empty());
+            assert!(tags.len() <= TagUnion::ARBITRARY_MAX_LEN);
+            assert!(tags.iter().all(Tag::is_valid));
+        }
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn test_proptest_roundtrip(union in TagUnion::arb()) {
+            proptest::prop_assert!(!union.is_empty());
+            proptest::prop_assert_eq!(TagUnion::from_str(union.to_string()), Ok(union));
+        }
+    }
+
+    #[test]
+    fn test_parse_many() {
+        let unions = TagUnion::parse_many("foo+bar\r\n\n  baz  \nqux+quux\n").unwrap();
+        assert_eq!(
+            unions,
+            [
+                TagUnion::from_str("foo+bar").unwrap(),
+                TagUnion::from_str("baz").unwrap(),
+                TagUnion::from_str("qux+quux").unwrap(),
+            ]
+        );
+
+        assert_eq!(
+            TagUnion::parse_many("foo\n\nbar+#baz"),
+            Err(TagUnionFromStringError::InvalidTagOnLine(
+                3,
+                TagFromStringError::MustStartAlphabetic('#', 4)
+            ))
+        );
+        assert_eq!(
+            TagUnion::parse_many("foo\n  bar+b_z"),
+            Err(TagUnionFromStringError::InvalidTagOnLine(
+                2,
+                TagFromStringError::InvalidCharacter('_', 7)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_matches_iter() {
+        let union = TagUnion::from_str("foo+bar").unwrap();
+        let tags = std::collections::BTreeSet::from([Tag::new("foo"), Tag::new("bar")]);
+        assert!(union.matches(&tags));
+        assert!(union.matches(&vec![Tag::new("bar"), Tag::new("baz"), Tag::new("foo")]));
+        assert!(!union.matches(&[Tag::new("foo")]));
+        assert!(TagUnion::default().matches(&[]));
+    }
+
+    #[test]
+    #[cfg(feature = "rkyv")]
+    fn test_rkyv_roundtrip() {
+        use rkyv::rancor::Error;
+
+        let tags = TagUnion::from_str("foo+bar+baz").unwrap();
+        let bytes = rkyv::to_bytes::<Error>(&tags).unwrap();
+        let archived = rkyv::access::<rkyv::Archived<TagUnion>, Error>(&bytes).unwrap();
+        let archived_tags: Vec<&str> = archived.iter().map(|tag| tag.as_str()).collect();
+        assert_eq!(archived_tags, ["bar", "baz", "foo"]);
+
+        let deserialized: TagUnion = rkyv::deserialize::<TagUnion, Error>(archived).unwrap();
+        assert_eq!(deserialized, tags);
+    }
+
+    #[test]
+    #[cfg(feature = "rkyv")]
+    fn test_rkyv_invalid() {
+        use rkyv::rancor::Error;
+
+        let values = vec![String::from("foo"), String::from("Bar")];
+        let bytes = rkyv::to_bytes::<Error>(&values).unwrap();
+        let archived = rkyv::access::<rkyv::Archived<TagUnion>, Error>(&bytes).unwrap();
+        assert!(rkyv::deserialize::<TagUnion, Error>(archived).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_as_sequence() {
+        let tags = TagUnion::from_str("foo+bar").unwrap();
+        let mut json = Vec::new();
+        as_sequence::serialize(&tags, &mut serde_json::Serializer::new(&mut json)).unwrap();
+        assert_eq!(json, br#"["bar","foo"]"#);
+
+        let mut deserializer = serde_json::Deserializer::from_slice(&json);
+        assert_eq!(as_sequence::deserialize(&mut deserializer).unwrap(), tags);
+
+        let mut deserializer = serde_json::Deserializer::from_str(r#"["foo","bar-"]"#);
+        assert!(as_sequence::deserialize(&mut deserializer).is_err());
+
+        let mut deserializer = serde_json::Deserializer::from_str(r#"["","foo"]"#);
+        let tags = as_sequence::deserialize(&mut deserializer).unwrap();
+        assert_eq!(tags, TagUnion::from_str("foo").unwrap());
+        assert!(!tags.contains(&Tag::EMPTY));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_as_array() {
+        let tags = TagUnion::from_str("foo+bar").unwrap();
+        let json = serde_json::json!(["bar", "foo"]);
+        assert_eq!(
+            as_array::serialize(&tags, serde_json::value::Serializer).unwrap(),
+            json
+        );
+        assert_eq!(as_array::deserialize(json).unwrap(), tags);
+    }
 }